@@ -4,26 +4,32 @@
 //! want to send any unnecessary requests to fetch the model’s data.
 //! For example, see this code:
 //!
-//! ```
-//! let user = oxid_roblox::user_from_id(1).await.unwrap();
-//! println!("Follower count: {}", user.follower_count().await.unwrap());
+//! ```no_run
+//! # use oxid_roblox::derives::UserDerive;
+//! # async fn run(client: oxid_roblox::RobloxClient) {
+//! let user = client.user_from_id(1).await.unwrap();
+//! println!("Follower count: {}", user.follower_count(&client).await.unwrap());
+//! # }
 //! ```
 //!
-//! The [user_from_id](crate::user_from_id) function will send a request to the Roblox API to fetch
+//! The [user_from_id](crate::RobloxClient::user_from_id) method will send a request to the Roblox API to fetch
 //! the user’s data, yet we do not access any of its fields; we only use it to
 //! call a method. This is where base structures come in handy:
 //!
-//! ```
+//! ```no_run
+//! # use oxid_roblox::derives::UserDerive;
+//! # async fn run(client: oxid_roblox::RobloxClient) {
 //! let user = oxid_roblox::base_user(1);
-//! println!("Follower count: {}", user.follower_count().await.unwrap());
+//! println!("Follower count: {}", user.follower_count(&client).await.unwrap());
+//! # }
 //! ```
 //!
 //! Instead of sending two requests, this code only sends one. This may be
 //! useful in situations where you could be rate-limited.
 //!
-//! To know which methods send requests, they are usually marked as `async`.
+//! To know which methods send requests, they are usually `async` or return a `Future`.
 //! Examples of methods that do not send requests are ones that return bases
-//! or a [PageIterator](crate::util::paging::PageIterator`).
+//! or a [PageIterator](crate::util::paging::PageIterator).
 
 mod base_asset;
 mod base_group;
@@ -0,0 +1,366 @@
+use std::{future::Future, sync::Arc};
+
+use lazy_static::lazy_static;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::{
+    models::{
+        Badge, EconomyAsset, Group, Place, Plugin, Presence, PreviousUsernamesSkinnyUser,
+        SkinnyUser, Universe, User,
+    },
+    util::{cache::ResponseCache, paging, MultiGetResult, RobloxResult, RobloxService},
+    RobloxClient,
+};
+
+use super::PageIterator;
+
+lazy_static! {
+    static ref DEFAULT_CLIENT: Client = Client::from(crate::default_client().clone());
+}
+
+/// Returns the process-wide blocking client used by the free functions of this module. It
+/// shares its session with [crate::default_client].
+pub fn default_client() -> &'static Client {
+    &DEFAULT_CLIENT
+}
+
+/// A blocking version of [RobloxClient].
+///
+/// Build it from a configured [RobloxClient] to change its settings:
+///
+/// ```no_run
+/// use oxid_roblox::{blocking::Client, RobloxClient};
+///
+/// let client = Client::from(RobloxClient::builder().roblosecurity("cookie").build());
+/// println!("{:?}", client.authenticated_user());
+/// ```
+///
+/// Cloning a client is cheap, and the clone shares its session and runtime with the original.
+#[derive(Clone)]
+pub struct Client {
+    client: RobloxClient,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Creates an unauthenticated client with the default settings.
+    pub fn new() -> Self {
+        Self::from(RobloxClient::new())
+    }
+
+    /// The asynchronous client this client sends its requests with.
+    pub fn async_client(&self) -> &RobloxClient {
+        &self.client
+    }
+
+    pub(crate) fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    pub(crate) fn pages<T, U>(&self, iterator: paging::PageIterator<T, U>) -> PageIterator<T, U>
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+        U: Clone + Send + 'static,
+    {
+        PageIterator::new(iterator, self.runtime.clone())
+    }
+
+    pub fn set_roblosecurity(&self, roblosecurity: &str) {
+        self.client.set_roblosecurity(roblosecurity);
+    }
+
+    pub fn set_api_key(&self, api_key: &str) {
+        self.client.set_api_key(api_key);
+    }
+
+    pub fn set_access_token(&self, access_token: &str) {
+        self.client.set_access_token(access_token);
+    }
+
+    pub fn base_url(&self, service: RobloxService) -> &str {
+        self.client.base_url(service)
+    }
+
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.client.cache()
+    }
+
+    pub fn search_users(
+        &self,
+        keyword: &str,
+    ) -> PageIterator<PreviousUsernamesSkinnyUser, PreviousUsernamesSkinnyUser> {
+        self.pages(self.client.search_users(keyword))
+    }
+
+    pub fn authenticated_user(&self) -> RobloxResult<SkinnyUser> {
+        self.block_on(self.client.authenticated_user())
+    }
+
+    pub fn users_from_ids(
+        &self,
+        user_ids: Vec<i64>,
+        exclude_banned_users: bool,
+    ) -> RobloxResult<Vec<SkinnyUser>> {
+        self.block_on(self.client.users_from_ids(user_ids, exclude_banned_users))
+    }
+
+    pub fn users_from_ids_keyed(
+        &self,
+        user_ids: Vec<i64>,
+        exclude_banned_users: bool,
+    ) -> RobloxResult<MultiGetResult<i64, SkinnyUser>> {
+        self.block_on(
+            self.client
+                .users_from_ids_keyed(user_ids, exclude_banned_users),
+        )
+    }
+
+    pub fn users_from_usernames(
+        &self,
+        usernames: Vec<&str>,
+        exclude_banned_users: bool,
+    ) -> RobloxResult<Vec<SkinnyUser>> {
+        self.block_on(
+            self.client
+                .users_from_usernames(usernames, exclude_banned_users),
+        )
+    }
+
+    pub fn users_from_usernames_keyed(
+        &self,
+        usernames: Vec<&str>,
+        exclude_banned_users: bool,
+    ) -> RobloxResult<MultiGetResult<String, SkinnyUser>> {
+        self.block_on(
+            self.client
+                .users_from_usernames_keyed(usernames, exclude_banned_users),
+        )
+    }
+
+    pub fn user_presences_from_ids(&self, user_ids: Vec<i64>) -> RobloxResult<Vec<Presence>> {
+        self.block_on(self.client.user_presences_from_ids(user_ids))
+    }
+
+    pub fn user_presences_from_ids_keyed(
+        &self,
+        user_ids: Vec<i64>,
+    ) -> RobloxResult<MultiGetResult<i64, Presence>> {
+        self.block_on(self.client.user_presences_from_ids_keyed(user_ids))
+    }
+
+    pub fn universes_from_ids(&self, universe_ids: Vec<i64>) -> RobloxResult<Vec<Universe>> {
+        self.block_on(self.client.universes_from_ids(universe_ids))
+    }
+
+    pub fn universes_from_ids_keyed(
+        &self,
+        universe_ids: Vec<i64>,
+    ) -> RobloxResult<MultiGetResult<i64, Universe>> {
+        self.block_on(self.client.universes_from_ids_keyed(universe_ids))
+    }
+
+    pub fn places_from_ids(&self, place_ids: Vec<i64>) -> RobloxResult<Vec<Place>> {
+        self.block_on(self.client.places_from_ids(place_ids))
+    }
+
+    pub fn places_from_ids_keyed(
+        &self,
+        place_ids: Vec<i64>,
+    ) -> RobloxResult<MultiGetResult<i64, Place>> {
+        self.block_on(self.client.places_from_ids_keyed(place_ids))
+    }
+
+    pub fn plugins_from_ids(&self, plugin_ids: Vec<i64>) -> RobloxResult<Vec<Plugin>> {
+        self.block_on(self.client.plugins_from_ids(plugin_ids))
+    }
+
+    pub fn plugins_from_ids_keyed(
+        &self,
+        plugin_ids: Vec<i64>,
+    ) -> RobloxResult<MultiGetResult<i64, Plugin>> {
+        self.block_on(self.client.plugins_from_ids_keyed(plugin_ids))
+    }
+
+    pub fn user_from_id(&self, user_id: i64) -> RobloxResult<User> {
+        self.block_on(self.client.user_from_id(user_id))
+    }
+
+    pub fn user_from_username(&self, username: &str) -> RobloxResult<Option<SkinnyUser>> {
+        self.block_on(self.client.user_from_username(username))
+    }
+
+    pub fn group_from_id(&self, group_id: i64) -> RobloxResult<Group> {
+        self.block_on(self.client.group_from_id(group_id))
+    }
+
+    pub fn user_presence_from_id(&self, user_id: i64) -> RobloxResult<Option<Presence>> {
+        self.block_on(self.client.user_presence_from_id(user_id))
+    }
+
+    pub fn universe_from_id(&self, universe_id: i64) -> RobloxResult<Option<Universe>> {
+        self.block_on(self.client.universe_from_id(universe_id))
+    }
+
+    pub fn place_from_id(&self, place_id: i64) -> RobloxResult<Option<Place>> {
+        self.block_on(self.client.place_from_id(place_id))
+    }
+
+    pub fn asset_from_id(&self, asset_id: i64) -> RobloxResult<EconomyAsset> {
+        self.block_on(self.client.asset_from_id(asset_id))
+    }
+
+    pub fn plugin_from_id(&self, plugin_id: i64) -> RobloxResult<Option<Plugin>> {
+        self.block_on(self.client.plugin_from_id(plugin_id))
+    }
+
+    pub fn badge_from_id(&self, badge_id: i64) -> RobloxResult<Badge> {
+        self.block_on(self.client.badge_from_id(badge_id))
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<RobloxClient> for Client {
+    fn from(client: RobloxClient) -> Self {
+        // A worker thread lets spawned tasks, such as the prefetching of pages, make progress
+        // between calls
+        let runtime = Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("failed to build the runtime of a blocking client");
+
+        Self {
+            client,
+            runtime: Arc::new(runtime),
+        }
+    }
+}
+
+// The functions below are shorthands for the methods of the default client
+
+pub fn set_roblosecurity(roblosecurity: &str) {
+    default_client().set_roblosecurity(roblosecurity);
+}
+
+pub fn set_api_key(api_key: &str) {
+    default_client().set_api_key(api_key);
+}
+
+pub fn set_access_token(access_token: &str) {
+    default_client().set_access_token(access_token);
+}
+
+pub fn search_users(
+    keyword: &str,
+) -> PageIterator<PreviousUsernamesSkinnyUser, PreviousUsernamesSkinnyUser> {
+    default_client().search_users(keyword)
+}
+
+pub fn authenticated_user() -> RobloxResult<SkinnyUser> {
+    default_client().authenticated_user()
+}
+
+pub fn users_from_ids(
+    user_ids: Vec<i64>,
+    exclude_banned_users: bool,
+) -> RobloxResult<Vec<SkinnyUser>> {
+    default_client().users_from_ids(user_ids, exclude_banned_users)
+}
+
+pub fn users_from_ids_keyed(
+    user_ids: Vec<i64>,
+    exclude_banned_users: bool,
+) -> RobloxResult<MultiGetResult<i64, SkinnyUser>> {
+    default_client().users_from_ids_keyed(user_ids, exclude_banned_users)
+}
+
+pub fn users_from_usernames(
+    usernames: Vec<&str>,
+    exclude_banned_users: bool,
+) -> RobloxResult<Vec<SkinnyUser>> {
+    default_client().users_from_usernames(usernames, exclude_banned_users)
+}
+
+pub fn users_from_usernames_keyed(
+    usernames: Vec<&str>,
+    exclude_banned_users: bool,
+) -> RobloxResult<MultiGetResult<String, SkinnyUser>> {
+    default_client().users_from_usernames_keyed(usernames, exclude_banned_users)
+}
+
+pub fn user_presences_from_ids(user_ids: Vec<i64>) -> RobloxResult<Vec<Presence>> {
+    default_client().user_presences_from_ids(user_ids)
+}
+
+pub fn user_presences_from_ids_keyed(
+    user_ids: Vec<i64>,
+) -> RobloxResult<MultiGetResult<i64, Presence>> {
+    default_client().user_presences_from_ids_keyed(user_ids)
+}
+
+pub fn universes_from_ids(universe_ids: Vec<i64>) -> RobloxResult<Vec<Universe>> {
+    default_client().universes_from_ids(universe_ids)
+}
+
+pub fn universes_from_ids_keyed(
+    universe_ids: Vec<i64>,
+) -> RobloxResult<MultiGetResult<i64, Universe>> {
+    default_client().universes_from_ids_keyed(universe_ids)
+}
+
+pub fn places_from_ids(place_ids: Vec<i64>) -> RobloxResult<Vec<Place>> {
+    default_client().places_from_ids(place_ids)
+}
+
+pub fn places_from_ids_keyed(place_ids: Vec<i64>) -> RobloxResult<MultiGetResult<i64, Place>> {
+    default_client().places_from_ids_keyed(place_ids)
+}
+
+pub fn plugins_from_ids(plugin_ids: Vec<i64>) -> RobloxResult<Vec<Plugin>> {
+    default_client().plugins_from_ids(plugin_ids)
+}
+
+pub fn plugins_from_ids_keyed(plugin_ids: Vec<i64>) -> RobloxResult<MultiGetResult<i64, Plugin>> {
+    default_client().plugins_from_ids_keyed(plugin_ids)
+}
+
+pub fn user_from_id(user_id: i64) -> RobloxResult<User> {
+    default_client().user_from_id(user_id)
+}
+
+pub fn user_from_username(username: &str) -> RobloxResult<Option<SkinnyUser>> {
+    default_client().user_from_username(username)
+}
+
+pub fn group_from_id(group_id: i64) -> RobloxResult<Group> {
+    default_client().group_from_id(group_id)
+}
+
+pub fn user_presence_from_id(user_id: i64) -> RobloxResult<Option<Presence>> {
+    default_client().user_presence_from_id(user_id)
+}
+
+pub fn universe_from_id(universe_id: i64) -> RobloxResult<Option<Universe>> {
+    default_client().universe_from_id(universe_id)
+}
+
+pub fn place_from_id(place_id: i64) -> RobloxResult<Option<Place>> {
+    default_client().place_from_id(place_id)
+}
+
+pub fn asset_from_id(asset_id: i64) -> RobloxResult<EconomyAsset> {
+    default_client().asset_from_id(asset_id)
+}
+
+pub fn plugin_from_id(plugin_id: i64) -> RobloxResult<Option<Plugin>> {
+    default_client().plugin_from_id(plugin_id)
+}
+
+pub fn badge_from_id(badge_id: i64) -> RobloxResult<Badge> {
+    default_client().badge_from_id(badge_id)
+}
@@ -0,0 +1,249 @@
+//! Blocking versions of the traits of [crate::derives]
+//!
+//! Each trait is implemented for every type that implements its asynchronous
+//! counterpart, so bringing it into scope is enough to call its methods.
+
+use crate::{
+    derives,
+    models::{
+        AssetResaleData, Badge, GamePass, GroupRole, GroupSettings, GroupShout, JoinRequest,
+        Member, SocialLink, UniverseLiveStats, WallPost,
+    },
+    util::{responses::UsernameHistoryResponse, RobloxResult},
+};
+
+use super::{Client, PageIterator};
+
+pub trait GroupDerive: derives::GroupDerive {
+    fn update_shout(&mut self, client: &Client, message: String) -> RobloxResult<GroupShout> {
+        client.block_on(derives::GroupDerive::update_shout(
+            self,
+            client.async_client(),
+            message,
+        ))
+    }
+
+    fn accept_join_request(&self, client: &Client, user_id: i64) -> RobloxResult<()> {
+        client.block_on(derives::GroupDerive::accept_join_request(
+            self,
+            client.async_client(),
+            user_id,
+        ))
+    }
+
+    fn decline_join_request(&self, client: &Client, user_id: i64) -> RobloxResult<()> {
+        client.block_on(derives::GroupDerive::decline_join_request(
+            self,
+            client.async_client(),
+            user_id,
+        ))
+    }
+
+    fn kick(&self, client: &Client, user_id: i64) -> RobloxResult<()> {
+        client.block_on(derives::GroupDerive::kick(
+            self,
+            client.async_client(),
+            user_id,
+        ))
+    }
+
+    fn roles(&self, client: &Client) -> RobloxResult<Vec<GroupRole>> {
+        client.block_on(derives::GroupDerive::roles(self, client.async_client()))
+    }
+
+    fn set_user_role(&self, client: &Client, user_id: i64, role_id: i64) -> RobloxResult<()> {
+        client.block_on(derives::GroupDerive::set_user_role(
+            self,
+            client.async_client(),
+            user_id,
+            role_id,
+        ))
+    }
+
+    fn members(&self, client: &Client) -> PageIterator<Member, Member> {
+        client.pages(derives::GroupDerive::members(self, client.async_client()))
+    }
+
+    fn settings(&self, client: &Client) -> RobloxResult<GroupSettings> {
+        client.block_on(derives::GroupDerive::settings(self, client.async_client()))
+    }
+
+    fn update_settings(
+        &self,
+        client: &Client,
+        is_approval_required: Option<bool>,
+        are_enemies_allowed: Option<bool>,
+        are_group_funds_visible: Option<bool>,
+        are_group_games_visible: Option<bool>,
+    ) -> RobloxResult<()> {
+        client.block_on(derives::GroupDerive::update_settings(
+            self,
+            client.async_client(),
+            is_approval_required,
+            are_enemies_allowed,
+            are_group_funds_visible,
+            are_group_games_visible,
+        ))
+    }
+
+    fn delete_all_wall_posts_from_user(&self, client: &Client, user_id: i64) -> RobloxResult<()> {
+        client.block_on(derives::GroupDerive::delete_all_wall_posts_from_user(
+            self,
+            client.async_client(),
+            user_id,
+        ))
+    }
+
+    fn delete_wall_post(&self, client: &Client, wall_post_id: i64) -> RobloxResult<()> {
+        client.block_on(derives::GroupDerive::delete_wall_post(
+            self,
+            client.async_client(),
+            wall_post_id,
+        ))
+    }
+
+    fn wall_posts(&self, client: &Client) -> PageIterator<WallPost, WallPost> {
+        client.pages(derives::GroupDerive::wall_posts(
+            self,
+            client.async_client(),
+        ))
+    }
+
+    fn join_requests(&self, client: &Client) -> PageIterator<JoinRequest, JoinRequest> {
+        client.pages(derives::GroupDerive::join_requests(
+            self,
+            client.async_client(),
+        ))
+    }
+
+    fn social_links(&self, client: &Client) -> RobloxResult<Vec<SocialLink>> {
+        client.block_on(derives::GroupDerive::social_links(
+            self,
+            client.async_client(),
+        ))
+    }
+
+    fn join_request_from_user(
+        &self,
+        client: &Client,
+        user_id: i64,
+    ) -> RobloxResult<Option<JoinRequest>> {
+        client.block_on(derives::GroupDerive::join_request_from_user(
+            self,
+            client.async_client(),
+            user_id,
+        ))
+    }
+}
+
+impl<T: derives::GroupDerive + ?Sized> GroupDerive for T {}
+
+pub trait UserDerive: derives::UserDerive {
+    fn currency(&self, client: &Client) -> RobloxResult<i64> {
+        client.block_on(derives::UserDerive::currency(self, client.async_client()))
+    }
+
+    fn has_premium(&self, client: &Client) -> RobloxResult<bool> {
+        client.block_on(derives::UserDerive::has_premium(
+            self,
+            client.async_client(),
+        ))
+    }
+
+    fn friend_count(&self, client: &Client) -> RobloxResult<i32> {
+        client.block_on(derives::UserDerive::friend_count(
+            self,
+            client.async_client(),
+        ))
+    }
+
+    fn follower_count(&self, client: &Client) -> RobloxResult<i32> {
+        client.block_on(derives::UserDerive::follower_count(
+            self,
+            client.async_client(),
+        ))
+    }
+
+    fn following_count(&self, client: &Client) -> RobloxResult<i32> {
+        client.block_on(derives::UserDerive::following_count(
+            self,
+            client.async_client(),
+        ))
+    }
+
+    fn username_history(&self, client: &Client) -> PageIterator<UsernameHistoryResponse, String> {
+        client.pages(derives::UserDerive::username_history(
+            self,
+            client.async_client(),
+        ))
+    }
+}
+
+impl<T: derives::UserDerive + ?Sized> UserDerive for T {}
+
+pub trait UniverseDerive: derives::UniverseDerive {
+    fn favorite_count(&self, client: &Client) -> RobloxResult<i64> {
+        client.block_on(derives::UniverseDerive::favorite_count(
+            self,
+            client.async_client(),
+        ))
+    }
+
+    fn badges(&self, client: &Client) -> PageIterator<Badge, Badge> {
+        client.pages(derives::UniverseDerive::badges(self, client.async_client()))
+    }
+
+    fn live_stats(&self, client: &Client) -> RobloxResult<UniverseLiveStats> {
+        client.block_on(derives::UniverseDerive::live_stats(
+            self,
+            client.async_client(),
+        ))
+    }
+
+    fn gamepasses(&self, client: &Client) -> PageIterator<GamePass, GamePass> {
+        client.pages(derives::UniverseDerive::gamepasses(
+            self,
+            client.async_client(),
+        ))
+    }
+
+    fn social_links(&self, client: &Client) -> RobloxResult<Vec<SocialLink>> {
+        client.block_on(derives::UniverseDerive::social_links(
+            self,
+            client.async_client(),
+        ))
+    }
+}
+
+impl<T: derives::UniverseDerive + ?Sized> UniverseDerive for T {}
+
+pub trait PluginDerive: derives::PluginDerive {
+    fn update(
+        &self,
+        client: &Client,
+        name: Option<&str>,
+        description: Option<&str>,
+        comments_enabled: Option<bool>,
+    ) -> RobloxResult<()> {
+        client.block_on(derives::PluginDerive::update(
+            self,
+            client.async_client(),
+            name,
+            description,
+            comments_enabled,
+        ))
+    }
+}
+
+impl<T: derives::PluginDerive + ?Sized> PluginDerive for T {}
+
+pub trait AssetDerive: derives::AssetDerive {
+    fn resale_data(&self, client: &Client) -> RobloxResult<AssetResaleData> {
+        client.block_on(derives::AssetDerive::resale_data(
+            self,
+            client.async_client(),
+        ))
+    }
+}
+
+impl<T: derives::AssetDerive + ?Sized> AssetDerive for T {}
@@ -0,0 +1,29 @@
+//! A blocking API, available with the `blocking` feature
+//!
+//! A blocking [Client] wraps a [RobloxClient](crate::RobloxClient) along with
+//! a runtime of its own, and waits for each request to complete. Its methods
+//! and the traits of [derives] mirror the asynchronous ones, and paginated
+//! endpoints return a [PageIterator] that can be used as a regular
+//! [Iterator]:
+//!
+//! ```no_run
+//! use oxid_roblox::blocking::{derives::GroupDerive, Client};
+//!
+//! let client = Client::new();
+//! let user = client.user_from_id(1).unwrap();
+//! println!("{}", user.name);
+//!
+//! for member in oxid_roblox::base_group(1).members(&client).into_iter().take(10) {
+//!     println!("{:?}", member.unwrap());
+//! }
+//! ```
+//!
+//! The blocking API must not be used from within an async runtime, as waiting
+//! for a request there would block the runtime itself.
+
+mod client;
+pub mod derives;
+mod paging;
+
+pub use client::*;
+pub use paging::{IntoIter, PageIterator};
@@ -0,0 +1,151 @@
+use std::{pin::Pin, sync::Arc};
+
+use futures_core::Stream;
+use futures_util::StreamExt;
+use tokio::runtime::Runtime;
+
+use crate::util::{
+    paging::{self, Page, PageIteratorState, PageSize, SortOrder},
+    RobloxResult,
+};
+
+/// A blocking version of [paging::PageIterator]. Convert it into an [Iterator] with
+/// [IntoIterator::into_iter] to fetch its items.
+pub struct PageIterator<T, U>
+where
+    T: serde::de::DeserializeOwned,
+    U: Clone,
+{
+    inner: paging::PageIterator<T, U>,
+    runtime: Arc<Runtime>,
+}
+
+impl<T, U> PageIterator<T, U>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+    U: Clone + Send + 'static,
+{
+    pub(crate) fn new(inner: paging::PageIterator<T, U>, runtime: Arc<Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    pub fn sort_order(mut self, sort_order: SortOrder) -> Self {
+        self.inner = self.inner.sort_order(sort_order);
+        self
+    }
+
+    pub fn page_size(mut self, page_size: PageSize) -> Self {
+        self.inner = self.inner.page_size(page_size);
+        self
+    }
+
+    /// Lets [IntoIterator::into_iter] and [PageIterator::into_pages_iter] fetch up to
+    /// `prefetch` pages ahead of the one being consumed, in the background.
+    pub fn prefetch(mut self, prefetch: usize) -> Self {
+        self.inner = self.inner.prefetch(prefetch);
+        self
+    }
+
+    /// Maps every item with `mapper`.
+    pub fn map<V>(self, mapper: impl FnMut(U) -> V + Send + 'static) -> PageIterator<T, V>
+    where
+        V: Clone + Send + 'static,
+    {
+        PageIterator::new(self.inner.map(mapper), self.runtime)
+    }
+
+    /// Maps every item with `mapper`, dropping those it returns `None` for.
+    pub fn filter_map<V>(
+        self,
+        mapper: impl FnMut(U) -> Option<V> + Send + 'static,
+    ) -> PageIterator<T, V>
+    where
+        V: Clone + Send + 'static,
+    {
+        PageIterator::new(self.inner.filter_map(mapper), self.runtime)
+    }
+
+    pub fn starting_at(mut self, cursor: &str) -> Self {
+        self.inner = self.inner.starting_at(cursor);
+        self
+    }
+
+    pub fn resume_from(mut self, state: PageIteratorState) -> Self {
+        self.inner = self.inner.resume_from(state);
+        self
+    }
+
+    pub fn cursor(&self) -> Option<&str> {
+        self.inner.cursor()
+    }
+
+    pub fn state(&self) -> PageIteratorState {
+        self.inner.state()
+    }
+
+    /// Fetches the next page, or returns `None` once the last one was fetched.
+    pub fn next_page(&mut self) -> RobloxResult<Option<Page<U>>> {
+        self.runtime.block_on(self.inner.next_page())
+    }
+
+    /// Fetches the page before the one last fetched, or returns `None` if that was the first
+    /// page.
+    pub fn previous_page(&mut self) -> RobloxResult<Option<Page<U>>> {
+        self.runtime.block_on(self.inner.previous_page())
+    }
+
+    /// Fetches the page of `cursor`, or the first page for `None`, without moving the
+    /// iteration.
+    pub fn fetch_page(&mut self, cursor: Option<&str>) -> RobloxResult<Page<U>> {
+        self.runtime.block_on(self.inner.fetch_page(cursor))
+    }
+
+    /// Like [IntoIterator::into_iter], but yields whole pages.
+    pub fn into_pages_iter(self) -> IntoIter<Page<U>> {
+        IntoIter {
+            stream: Box::pin(self.inner.into_pages_stream()),
+            runtime: self.runtime,
+        }
+    }
+
+    /// Fetches every remaining item.
+    pub fn collect_all(self) -> RobloxResult<Vec<U>> {
+        self.runtime.block_on(self.inner.collect_all())
+    }
+
+    /// Fetches the next `n` items, or every remaining item if there are fewer.
+    pub fn take_n(self, n: usize) -> RobloxResult<Vec<U>> {
+        self.runtime.block_on(self.inner.take_n(n))
+    }
+}
+
+impl<T, U> IntoIterator for PageIterator<T, U>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+    U: Clone + Send + 'static,
+{
+    type Item = RobloxResult<U>;
+    type IntoIter = IntoIter<U>;
+
+    fn into_iter(self) -> IntoIter<U> {
+        IntoIter {
+            stream: Box::pin(self.inner.into_stream()),
+            runtime: self.runtime,
+        }
+    }
+}
+
+/// An [Iterator] over the items of a [PageIterator], fetching a new page whenever the
+/// previous one runs out.
+pub struct IntoIter<U> {
+    stream: Pin<Box<dyn Stream<Item = RobloxResult<U>> + Send>>,
+    runtime: Arc<Runtime>,
+}
+
+impl<U> Iterator for IntoIter<U> {
+    type Item = RobloxResult<U>;
+
+    fn next(&mut self) -> Option<RobloxResult<U>> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
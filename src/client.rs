@@ -1,5 +1,15 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    future::Future,
+    hash::Hash,
+};
+
+use futures_util::{stream, StreamExt, TryStreamExt};
+use serde_json::json;
+
 use crate::{
     bases::{BaseAsset, BaseGroup, BasePlugin, BaseUniverse, BaseUser},
+    default_client,
     models::{
         Badge, EconomyAsset, Group, Place, Plugin, Presence, PreviousUsernamesSkinnyUser,
         SkinnyUser, Universe, User,
@@ -7,36 +17,476 @@ use crate::{
     util::{
         api_helper,
         paging::{identity_mapper, PageIterator},
-        responses::{ApiArrayResponse, UserPresencesResponse},
-        ResultExtensions, RobloxResult,
+        responses::{ApiArrayResponse, UserPresencesResponse, UsernameUserResponse},
+        MultiGetResult, RobloxResult, RobloxService,
     },
+    RobloxClient,
 };
-use serde_json::json;
+
+// The most ids Roblox accepts in one request of each multi-get endpoint
+const USERS_BATCH_SIZE: usize = 100;
+const USERNAMES_BATCH_SIZE: usize = 100;
+const USER_PRESENCES_BATCH_SIZE: usize = 50;
+const UNIVERSES_BATCH_SIZE: usize = 50;
+const PLACES_BATCH_SIZE: usize = 50;
+const PLUGINS_BATCH_SIZE: usize = 50;
 
 // Transforms a list of ids into a comma-separated string "1,2,3,4,5" for use in some multi-get endpoints
 fn ids_to_string(ids: Vec<i64>) -> String {
-    let mut s = ids
-        .iter()
-        .map(|id| format!("{},", id.to_string()))
-        .collect::<String>();
+    let mut s = ids.iter().map(|id| format!("{},", id)).collect::<String>();
     s.pop();
     s
 }
 
+impl RobloxClient {
+    pub fn search_users(
+        &self,
+        keyword: &str,
+    ) -> PageIterator<PreviousUsernamesSkinnyUser, PreviousUsernamesSkinnyUser> {
+        PageIterator::new(
+            self.clone(),
+            self.endpoint(
+                RobloxService::Users,
+                &format!(
+                    "/v1/users/search?{}",
+                    api_helper::urlencode(&[("keyword", keyword)])
+                ),
+            ),
+            identity_mapper,
+        )
+    }
+
+    pub async fn authenticated_user(&self) -> RobloxResult<SkinnyUser> {
+        api_helper::authenticated_get(
+            self,
+            self.endpoint(RobloxService::Users, "/v1/users/authenticated"),
+        )
+        .await
+        .and_then(api_helper::deserialize_body)
+    }
+
+    async fn users_batch(
+        &self,
+        user_ids: Vec<i64>,
+        exclude_banned_users: bool,
+    ) -> RobloxResult<Vec<SkinnyUser>> {
+        api_helper::post(
+            self,
+            self.endpoint(RobloxService::Users, "/v1/users"),
+            json!({
+                "userIds": user_ids,
+                "excludeBannedUsers": exclude_banned_users
+            }),
+        )
+        .await
+        .and_then(api_helper::deserialize_body::<ApiArrayResponse<SkinnyUser>>)
+        .map(|data| data.data)
+    }
+
+    async fn usernames_batch(
+        &self,
+        usernames: Vec<String>,
+        exclude_banned_users: bool,
+    ) -> RobloxResult<Vec<UsernameUserResponse>> {
+        api_helper::post(
+            self,
+            self.endpoint(RobloxService::Users, "/v1/usernames/users"),
+            json!({
+                "usernames": usernames,
+                "excludeBannedUsers": exclude_banned_users
+            }),
+        )
+        .await
+        .and_then(api_helper::deserialize_body::<ApiArrayResponse<UsernameUserResponse>>)
+        .map(|data| data.data)
+    }
+
+    async fn user_presences_batch(&self, user_ids: Vec<i64>) -> RobloxResult<Vec<Presence>> {
+        api_helper::post(
+            self,
+            self.endpoint(RobloxService::Presence, "/v1/presence/users"),
+            json!({
+                "userIds": user_ids
+            }),
+        )
+        .await
+        .and_then(api_helper::deserialize_body::<UserPresencesResponse>)
+        .map(|data| data.user_presences)
+    }
+
+    async fn universes_batch(&self, universe_ids: Vec<i64>) -> RobloxResult<Vec<Universe>> {
+        api_helper::get(
+            self,
+            self.endpoint(
+                RobloxService::Games,
+                &format!("/v1/games?universeIds={}", ids_to_string(universe_ids)),
+            ),
+        )
+        .await
+        .and_then(api_helper::deserialize_body::<ApiArrayResponse<Universe>>)
+        .map(|data| data.data)
+    }
+
+    async fn places_batch(&self, place_ids: Vec<i64>) -> RobloxResult<Vec<Place>> {
+        api_helper::get(
+            self,
+            self.endpoint(
+                RobloxService::Games,
+                &format!(
+                    "/v1/games/multiget-place-details?placeIds={}",
+                    ids_to_string(place_ids)
+                ),
+            ),
+        )
+        .await
+        .and_then(api_helper::deserialize_body::<Vec<Place>>)
+    }
+
+    async fn plugins_batch(&self, plugin_ids: Vec<i64>) -> RobloxResult<Vec<Plugin>> {
+        api_helper::get(
+            self,
+            self.endpoint(
+                RobloxService::Develop,
+                &format!("/v1/plugins?pluginIds={}", ids_to_string(plugin_ids)),
+            ),
+        )
+        .await
+        .and_then(api_helper::deserialize_body::<ApiArrayResponse<Plugin>>)
+        .map(|data| data.data)
+    }
+
+    // Fetches `keys`, which must be unique, in batches of at most `batch_size` with bounded concurrency
+    async fn fetch_batches<K, V, Fut>(
+        &self,
+        keys: &[K],
+        batch_size: usize,
+        fetch_batch: impl FnMut(Vec<K>) -> Fut,
+    ) -> RobloxResult<Vec<V>>
+    where
+        K: Clone,
+        Fut: Future<Output = RobloxResult<Vec<V>>>,
+    {
+        stream::iter(keys.chunks(batch_size).map(<[K]>::to_vec))
+            .map(fetch_batch)
+            .buffered(self.multi_get_concurrency())
+            .try_concat()
+            .await
+    }
+
+    // Fetches the unique keys of `keys` and returns the results in the order of `keys`
+    async fn multi_get<K, V, Fut>(
+        &self,
+        keys: Vec<K>,
+        batch_size: usize,
+        key_of: impl Fn(&V) -> K,
+        fetch_batch: impl FnMut(Vec<K>) -> Fut,
+    ) -> RobloxResult<Vec<V>>
+    where
+        K: Eq + Hash + Clone,
+        Fut: Future<Output = RobloxResult<Vec<V>>>,
+    {
+        let mut positions = HashMap::new();
+        let mut unique_keys = Vec::new();
+        for key in keys {
+            if let Entry::Vacant(entry) = positions.entry(key.clone()) {
+                entry.insert(unique_keys.len());
+                unique_keys.push(key);
+            }
+        }
+
+        let mut values = self
+            .fetch_batches(&unique_keys, batch_size, fetch_batch)
+            .await?;
+        values.sort_by_key(|value| positions.get(&key_of(value)).copied().unwrap_or(usize::MAX));
+        Ok(values)
+    }
+
+    // Fetches the unique keys of `keys` and sorts them into found and missing ones
+    async fn multi_get_keyed<K, V, Fut>(
+        &self,
+        keys: Vec<K>,
+        batch_size: usize,
+        key_of: impl Fn(&V) -> K,
+        fetch_batch: impl FnMut(Vec<K>) -> Fut,
+    ) -> RobloxResult<MultiGetResult<K, V>>
+    where
+        K: Eq + Hash + Clone,
+        Fut: Future<Output = RobloxResult<Vec<V>>>,
+    {
+        let mut result = MultiGetResult::new();
+        let mut unique_keys = Vec::new();
+        for key in keys {
+            if result.missing.insert(key.clone()) {
+                unique_keys.push(key);
+            }
+        }
+
+        for value in self
+            .fetch_batches(&unique_keys, batch_size, fetch_batch)
+            .await?
+        {
+            let key = key_of(&value);
+            if result.missing.remove(&key) {
+                result.found.insert(key, value);
+            }
+        }
+        Ok(result)
+    }
+
+    pub async fn users_from_ids(
+        &self,
+        user_ids: Vec<i64>,
+        exclude_banned_users: bool,
+    ) -> RobloxResult<Vec<SkinnyUser>> {
+        self.multi_get(
+            user_ids,
+            USERS_BATCH_SIZE,
+            |user: &SkinnyUser| user.id,
+            |batch| self.users_batch(batch, exclude_banned_users),
+        )
+        .await
+    }
+
+    pub async fn users_from_ids_keyed(
+        &self,
+        user_ids: Vec<i64>,
+        exclude_banned_users: bool,
+    ) -> RobloxResult<MultiGetResult<i64, SkinnyUser>> {
+        self.multi_get_keyed(
+            user_ids,
+            USERS_BATCH_SIZE,
+            |user: &SkinnyUser| user.id,
+            |batch| self.users_batch(batch, exclude_banned_users),
+        )
+        .await
+    }
+
+    /// Usernames are matched case-insensitively, and can also match a previous username
+    /// of a user.
+    pub async fn users_from_usernames(
+        &self,
+        usernames: Vec<&str>,
+        exclude_banned_users: bool,
+    ) -> RobloxResult<Vec<SkinnyUser>> {
+        self.multi_get(
+            usernames.iter().map(|name| name.to_lowercase()).collect(),
+            USERNAMES_BATCH_SIZE,
+            |user: &UsernameUserResponse| user.requested_username.to_lowercase(),
+            |batch| self.usernames_batch(batch, exclude_banned_users),
+        )
+        .await
+        .map(|users| users.into_iter().map(|user| user.user).collect())
+    }
+
+    /// Like [RobloxClient::users_from_usernames], keyed by the usernames as they were passed.
+    pub async fn users_from_usernames_keyed(
+        &self,
+        usernames: Vec<&str>,
+        exclude_banned_users: bool,
+    ) -> RobloxResult<MultiGetResult<String, SkinnyUser>> {
+        let lowercase_result = self
+            .multi_get_keyed(
+                usernames.iter().map(|name| name.to_lowercase()).collect(),
+                USERNAMES_BATCH_SIZE,
+                |user: &UsernameUserResponse| user.requested_username.to_lowercase(),
+                |batch| self.usernames_batch(batch, exclude_banned_users),
+            )
+            .await?;
+
+        let mut result = MultiGetResult::new();
+        for username in usernames {
+            match lowercase_result.get(&username.to_lowercase()) {
+                Some(user) => {
+                    result.found.insert(username.to_owned(), user.user.clone());
+                }
+                None => {
+                    result.missing.insert(username.to_owned());
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    pub async fn user_presences_from_ids(&self, user_ids: Vec<i64>) -> RobloxResult<Vec<Presence>> {
+        self.multi_get(
+            user_ids,
+            USER_PRESENCES_BATCH_SIZE,
+            |presence: &Presence| presence.user_id,
+            |batch| self.user_presences_batch(batch),
+        )
+        .await
+    }
+
+    pub async fn user_presences_from_ids_keyed(
+        &self,
+        user_ids: Vec<i64>,
+    ) -> RobloxResult<MultiGetResult<i64, Presence>> {
+        self.multi_get_keyed(
+            user_ids,
+            USER_PRESENCES_BATCH_SIZE,
+            |presence: &Presence| presence.user_id,
+            |batch| self.user_presences_batch(batch),
+        )
+        .await
+    }
+
+    pub async fn universes_from_ids(&self, universe_ids: Vec<i64>) -> RobloxResult<Vec<Universe>> {
+        self.multi_get(
+            universe_ids,
+            UNIVERSES_BATCH_SIZE,
+            |universe: &Universe| universe.id,
+            |batch| self.universes_batch(batch),
+        )
+        .await
+    }
+
+    pub async fn universes_from_ids_keyed(
+        &self,
+        universe_ids: Vec<i64>,
+    ) -> RobloxResult<MultiGetResult<i64, Universe>> {
+        self.multi_get_keyed(
+            universe_ids,
+            UNIVERSES_BATCH_SIZE,
+            |universe: &Universe| universe.id,
+            |batch| self.universes_batch(batch),
+        )
+        .await
+    }
+
+    pub async fn places_from_ids(&self, place_ids: Vec<i64>) -> RobloxResult<Vec<Place>> {
+        self.multi_get(
+            place_ids,
+            PLACES_BATCH_SIZE,
+            |place: &Place| place.id,
+            |batch| self.places_batch(batch),
+        )
+        .await
+    }
+
+    pub async fn places_from_ids_keyed(
+        &self,
+        place_ids: Vec<i64>,
+    ) -> RobloxResult<MultiGetResult<i64, Place>> {
+        self.multi_get_keyed(
+            place_ids,
+            PLACES_BATCH_SIZE,
+            |place: &Place| place.id,
+            |batch| self.places_batch(batch),
+        )
+        .await
+    }
+
+    pub async fn plugins_from_ids(&self, plugin_ids: Vec<i64>) -> RobloxResult<Vec<Plugin>> {
+        self.multi_get(
+            plugin_ids,
+            PLUGINS_BATCH_SIZE,
+            |plugin: &Plugin| plugin.id,
+            |batch| self.plugins_batch(batch),
+        )
+        .await
+    }
+
+    pub async fn plugins_from_ids_keyed(
+        &self,
+        plugin_ids: Vec<i64>,
+    ) -> RobloxResult<MultiGetResult<i64, Plugin>> {
+        self.multi_get_keyed(
+            plugin_ids,
+            PLUGINS_BATCH_SIZE,
+            |plugin: &Plugin| plugin.id,
+            |batch| self.plugins_batch(batch),
+        )
+        .await
+    }
+
+    pub async fn user_from_id(&self, user_id: i64) -> RobloxResult<User> {
+        api_helper::get(
+            self,
+            self.endpoint(RobloxService::Users, &format!("/v1/users/{}", user_id)),
+        )
+        .await
+        .and_then(api_helper::deserialize_body)
+    }
+
+    pub async fn user_from_username(&self, username: &str) -> RobloxResult<Option<SkinnyUser>> {
+        self.users_from_usernames_keyed(vec![username], false)
+            .await
+            .map(|mut users| users.found.remove(username))
+    }
+
+    pub async fn group_from_id(&self, group_id: i64) -> RobloxResult<Group> {
+        api_helper::get(
+            self,
+            self.endpoint(RobloxService::Groups, &format!("/v1/groups/{}", group_id)),
+        )
+        .await
+        .and_then(api_helper::deserialize_body)
+    }
+
+    pub async fn user_presence_from_id(&self, user_id: i64) -> RobloxResult<Option<Presence>> {
+        self.user_presences_from_ids_keyed(vec![user_id])
+            .await
+            .map(|mut presences| presences.found.remove(&user_id))
+    }
+
+    pub async fn universe_from_id(&self, universe_id: i64) -> RobloxResult<Option<Universe>> {
+        self.universes_from_ids_keyed(vec![universe_id])
+            .await
+            .map(|mut universes| universes.found.remove(&universe_id))
+    }
+
+    pub async fn place_from_id(&self, place_id: i64) -> RobloxResult<Option<Place>> {
+        self.places_from_ids_keyed(vec![place_id])
+            .await
+            .map(|mut places| places.found.remove(&place_id))
+    }
+
+    pub async fn asset_from_id(&self, asset_id: i64) -> RobloxResult<EconomyAsset> {
+        api_helper::get(
+            self,
+            self.endpoint(
+                RobloxService::Economy,
+                &format!("/v2/assets/{}/details", asset_id),
+            ),
+        )
+        .await
+        .and_then(api_helper::deserialize_body)
+    }
+
+    pub async fn plugin_from_id(&self, plugin_id: i64) -> RobloxResult<Option<Plugin>> {
+        self.plugins_from_ids_keyed(vec![plugin_id])
+            .await
+            .map(|mut plugins| plugins.found.remove(&plugin_id))
+    }
+
+    pub async fn badge_from_id(&self, badge_id: i64) -> RobloxResult<Badge> {
+        api_helper::get(
+            self,
+            self.endpoint(RobloxService::Badges, &format!("/v1/badges/{}", badge_id)),
+        )
+        .await
+        .and_then(api_helper::deserialize_body)
+    }
+}
+
+// The functions below are shorthands for the methods of the default client
+
 pub fn set_roblosecurity(roblosecurity: &str) {
-    api_helper::set_roblosecurity(roblosecurity);
+    default_client().set_roblosecurity(roblosecurity);
+}
+
+pub fn set_api_key(api_key: &str) {
+    default_client().set_api_key(api_key);
+}
+
+pub fn set_access_token(access_token: &str) {
+    default_client().set_access_token(access_token);
 }
 
 pub fn search_users(
     keyword: &str,
 ) -> PageIterator<PreviousUsernamesSkinnyUser, PreviousUsernamesSkinnyUser> {
-    PageIterator::new(
-        format!(
-            "https://users.roblox.com/v1/users/search?keyword={}",
-            keyword
-        ),
-        identity_mapper,
-    )
+    default_client().search_users(keyword)
 }
 
 pub fn base_plugin(plugin_id: i64) -> BasePlugin {
@@ -59,151 +509,252 @@ pub fn base_asset(asset_id: i64) -> BaseAsset {
     BaseAsset { id: asset_id }
 }
 
-pub async fn authenticated_user() -> SkinnyUser {
-    api_helper::deserialize_body(
-        api_helper::get("https://users.roblox.com/v1/users/authenticated".to_owned())
-            .await
-            .unwrap(),
-    )
-    .await
+pub async fn authenticated_user() -> RobloxResult<SkinnyUser> {
+    default_client().authenticated_user().await
 }
 
 pub async fn users_from_ids(
     user_ids: Vec<i64>,
     exclude_banned_users: bool,
 ) -> RobloxResult<Vec<SkinnyUser>> {
-    api_helper::post(
-        "https://users.roblox.com/v1/users".to_owned(),
-        json!({
-            "userIds": user_ids,
-            "excludeBannedUsers": exclude_banned_users
-        }),
-    )
-    .await
-    .map_async(api_helper::deserialize_body::<ApiArrayResponse<SkinnyUser>>)
-    .await
-    .map(|data| data.data)
+    default_client()
+        .users_from_ids(user_ids, exclude_banned_users)
+        .await
+}
+
+pub async fn users_from_ids_keyed(
+    user_ids: Vec<i64>,
+    exclude_banned_users: bool,
+) -> RobloxResult<MultiGetResult<i64, SkinnyUser>> {
+    default_client()
+        .users_from_ids_keyed(user_ids, exclude_banned_users)
+        .await
 }
 
 pub async fn users_from_usernames(
     usernames: Vec<&str>,
     exclude_banned_users: bool,
 ) -> RobloxResult<Vec<SkinnyUser>> {
-    api_helper::post(
-        "https://users.roblox.com/v1/usernames/users".to_owned(),
-        json!({
-            "usernames": usernames,
-            "excludeBannedUsers": exclude_banned_users
-        }),
-    )
-    .await
-    .map_async(api_helper::deserialize_body::<ApiArrayResponse<SkinnyUser>>)
-    .await
-    .map(|data| data.data)
+    default_client()
+        .users_from_usernames(usernames, exclude_banned_users)
+        .await
+}
+
+pub async fn users_from_usernames_keyed(
+    usernames: Vec<&str>,
+    exclude_banned_users: bool,
+) -> RobloxResult<MultiGetResult<String, SkinnyUser>> {
+    default_client()
+        .users_from_usernames_keyed(usernames, exclude_banned_users)
+        .await
 }
 
 pub async fn user_presences_from_ids(user_ids: Vec<i64>) -> RobloxResult<Vec<Presence>> {
-    api_helper::post(
-        "https://presence.roblox.com/v1/presence/users".to_owned(),
-        json!({
-            "userIds": user_ids
-        }),
-    )
-    .await
-    .map_async(api_helper::deserialize_body::<UserPresencesResponse>)
-    .await
-    .map(|data| data.user_presences)
+    default_client().user_presences_from_ids(user_ids).await
+}
+
+pub async fn user_presences_from_ids_keyed(
+    user_ids: Vec<i64>,
+) -> RobloxResult<MultiGetResult<i64, Presence>> {
+    default_client()
+        .user_presences_from_ids_keyed(user_ids)
+        .await
 }
 
 pub async fn universes_from_ids(universe_ids: Vec<i64>) -> RobloxResult<Vec<Universe>> {
-    api_helper::get(format!(
-        "https://games.roblox.com/v1/games?universeIds={}",
-        ids_to_string(universe_ids)
-    ))
-    .await
-    .map_async(api_helper::deserialize_body::<ApiArrayResponse<Universe>>)
-    .await
-    .map(|data| data.data)
+    default_client().universes_from_ids(universe_ids).await
+}
+
+pub async fn universes_from_ids_keyed(
+    universe_ids: Vec<i64>,
+) -> RobloxResult<MultiGetResult<i64, Universe>> {
+    default_client()
+        .universes_from_ids_keyed(universe_ids)
+        .await
 }
 
 pub async fn places_from_ids(place_ids: Vec<i64>) -> RobloxResult<Vec<Place>> {
-    api_helper::get(format!(
-        "https://games.roblox.com/v1/games/multiget-place-details?placeIds={}",
-        ids_to_string(place_ids)
-    ))
-    .await
-    .map_async(api_helper::deserialize_body::<Vec<Place>>)
-    .await
+    default_client().places_from_ids(place_ids).await
+}
+
+pub async fn places_from_ids_keyed(
+    place_ids: Vec<i64>,
+) -> RobloxResult<MultiGetResult<i64, Place>> {
+    default_client().places_from_ids_keyed(place_ids).await
 }
 
 pub async fn plugins_from_ids(plugin_ids: Vec<i64>) -> RobloxResult<Vec<Plugin>> {
-    api_helper::get(format!(
-        "https://develop.roblox.com/v1/plugins?pluginIds={}",
-        ids_to_string(plugin_ids)
-    ))
-    .await
-    .map_async(api_helper::deserialize_body::<ApiArrayResponse<Plugin>>)
-    .await
-    .map(|data| data.data)
+    default_client().plugins_from_ids(plugin_ids).await
+}
+
+pub async fn plugins_from_ids_keyed(
+    plugin_ids: Vec<i64>,
+) -> RobloxResult<MultiGetResult<i64, Plugin>> {
+    default_client().plugins_from_ids_keyed(plugin_ids).await
+}
+
+pub async fn load_user(user_id: i64) -> RobloxResult<Option<SkinnyUser>> {
+    default_client().load_user(user_id).await
+}
+
+pub async fn load_user_presence(user_id: i64) -> RobloxResult<Option<Presence>> {
+    default_client().load_user_presence(user_id).await
+}
+
+pub async fn load_universe(universe_id: i64) -> RobloxResult<Option<Universe>> {
+    default_client().load_universe(universe_id).await
 }
 
 pub async fn user_from_id(user_id: i64) -> RobloxResult<User> {
-    api_helper::get(format!("https://users.roblox.com/v1/users/{}", user_id))
-        .await
-        .map_async(api_helper::deserialize_body)
-        .await
+    default_client().user_from_id(user_id).await
 }
 
 pub async fn user_from_username(username: &str) -> RobloxResult<Option<SkinnyUser>> {
-    users_from_usernames(vec![username], false)
-        .await
-        .map(|users| users.first().cloned())
+    default_client().user_from_username(username).await
 }
 
 pub async fn group_from_id(group_id: i64) -> RobloxResult<Group> {
-    api_helper::get(format!("https://groups.roblox.com/v1/groups/{}", group_id))
-        .await
-        .map_async(api_helper::deserialize_body)
-        .await
+    default_client().group_from_id(group_id).await
 }
 
 pub async fn user_presence_from_id(user_id: i64) -> RobloxResult<Option<Presence>> {
-    user_presences_from_ids(vec![user_id])
-        .await
-        .map(|presences| presences.first().cloned())
+    default_client().user_presence_from_id(user_id).await
 }
 
 pub async fn universe_from_id(universe_id: i64) -> RobloxResult<Option<Universe>> {
-    universes_from_ids(vec![universe_id])
-        .await
-        .map(|universes| universes.first().cloned())
+    default_client().universe_from_id(universe_id).await
 }
 
 pub async fn place_from_id(place_id: i64) -> RobloxResult<Option<Place>> {
-    places_from_ids(vec![place_id])
-        .await
-        .map(|places| places.first().cloned())
+    default_client().place_from_id(place_id).await
 }
 
 pub async fn asset_from_id(asset_id: i64) -> RobloxResult<EconomyAsset> {
-    api_helper::get(format!(
-        "https://economy.roblox.com/v2/assets/{}/details",
-        asset_id
-    ))
-    .await
-    .map_async(api_helper::deserialize_body)
-    .await
+    default_client().asset_from_id(asset_id).await
 }
 
 pub async fn plugin_from_id(plugin_id: i64) -> RobloxResult<Option<Plugin>> {
-    plugins_from_ids(vec![plugin_id])
-        .await
-        .map(|plugins| plugins.first().cloned())
+    default_client().plugin_from_id(plugin_id).await
 }
 
 pub async fn badge_from_id(badge_id: i64) -> RobloxResult<Badge> {
-    api_helper::get(format!("https://badges.roblox.com/v1/badges/{}", badge_id))
-        .await
-        .map_async(api_helper::deserialize_body)
-        .await
+    default_client().badge_from_id(badge_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        sync::{Arc, Mutex},
+    };
+
+    use async_trait::async_trait;
+    use reqwest::{header::HeaderMap, StatusCode};
+    use serde_json::Value;
+
+    use super::*;
+    use crate::util::transport::{Request, Response, Transport, TransportError};
+
+    // A users endpoint that answers in reverse order, knows no user with an id above 1000 and
+    // records the ids of every batch
+    #[derive(Default)]
+    struct UsersTransport {
+        batches: Mutex<Vec<Vec<i64>>>,
+    }
+
+    #[async_trait]
+    impl Transport for UsersTransport {
+        async fn send(&self, request: Request) -> Result<Response, TransportError> {
+            // Such as the x-csrf-token fetch
+            if !request.url.ends_with("/v1/users") {
+                return Ok(Response {
+                    status: StatusCode::OK,
+                    headers: HeaderMap::new(),
+                    body: Vec::new(),
+                });
+            }
+            let body = serde_json::from_slice::<Value>(&request.body.unwrap_or_default())?;
+            let user_ids = serde_json::from_value::<Vec<i64>>(body["userIds"].clone())?;
+            self.batches.lock().unwrap().push(user_ids.clone());
+
+            let users = user_ids
+                .iter()
+                .rev()
+                .filter(|id| **id <= 1000)
+                .map(|id| json!({ "id": id, "name": format!("user{}", id), "displayName": "user" }))
+                .collect::<Vec<_>>();
+            Ok(Response {
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+                body: json!({ "data": users }).to_string().into_bytes(),
+            })
+        }
+    }
+
+    fn client() -> (RobloxClient, Arc<UsersTransport>) {
+        let transport = Arc::new(UsersTransport::default());
+        let client = RobloxClient::builder().transport(transport.clone()).build();
+        (client, transport)
+    }
+
+    #[tokio::test]
+    async fn multi_get_keeps_the_order_of_the_keys_and_deduplicates_them() {
+        let (client, transport) = client();
+        let users = client
+            .users_from_ids(vec![5, 1, 5, 2001, 2, 1], false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            users.iter().map(|user| user.id).collect::<Vec<_>>(),
+            vec![5, 1, 2]
+        );
+        assert_eq!(
+            *transport.batches.lock().unwrap(),
+            vec![vec![5, 1, 2001, 2]]
+        );
+    }
+
+    #[tokio::test]
+    async fn multi_get_splits_keys_into_batches() {
+        let (client, transport) = client();
+        let user_ids = (1..=250).collect::<Vec<_>>();
+        let users = client
+            .users_from_ids(user_ids.clone(), false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            users.iter().map(|user| user.id).collect::<Vec<_>>(),
+            user_ids
+        );
+        let mut batch_sizes = transport
+            .batches
+            .lock()
+            .unwrap()
+            .iter()
+            .map(Vec::len)
+            .collect::<Vec<_>>();
+        batch_sizes.sort();
+        assert_eq!(batch_sizes, vec![50, USERS_BATCH_SIZE, USERS_BATCH_SIZE]);
+    }
+
+    #[tokio::test]
+    async fn multi_get_keyed_sorts_keys_into_found_and_missing() {
+        let (client, transport) = client();
+        let result = client
+            .users_from_ids_keyed(vec![3, 2001, 3, 4, 2002], false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.found.keys().copied().collect::<HashSet<_>>(),
+            HashSet::from([3, 4])
+        );
+        assert_eq!(result.get(&4).unwrap().name, "user4");
+        assert_eq!(result.missing, HashSet::from([2001, 2002]));
+        assert!(!result.is_complete());
+        assert_eq!(transport.batches.lock().unwrap().len(), 1);
+        assert_eq!(transport.batches.lock().unwrap()[0].len(), 4);
+    }
 }
@@ -1,21 +1,27 @@
-use async_trait::async_trait;
+use std::future::Future;
 
 use crate::{
     models::AssetResaleData,
-    util::{api_helper, ResultExtensions, RobloxResult},
+    util::{api_helper, RobloxResult, RobloxService},
+    RobloxClient,
 };
 
-#[async_trait(?Send)]
 pub trait Asset {
     #[doc(hidden)]
     fn id(&self) -> i64;
-    async fn resale_data(&self) -> RobloxResult<AssetResaleData> {
-        api_helper::get(format!(
-            "https://economy.roblox.com/v1/assets/{}/resale-data",
-            self.id()
-        ))
-        .await
-        .map_async(api_helper::deserialize_body)
-        .await
+    fn resale_data(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<AssetResaleData>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Economy,
+            &format!("/v1/assets/{}/resale-data", self.id()),
+        );
+        async move {
+            api_helper::get(&client, url)
+                .await
+                .and_then(api_helper::deserialize_body)
+        }
     }
 }
@@ -1,192 +1,268 @@
+use std::future::Future;
+
 use crate::{
     models::{GroupRole, GroupSettings, GroupShout, JoinRequest, Member, SocialLink, WallPost},
     util::{
         api_helper,
         paging::{identity_mapper, PageIterator},
         responses::{ApiArrayResponse, GroupRolesResponse},
-        ResultExtensions, RobloxResult,
+        RobloxResult, RobloxService,
     },
+    RobloxClient,
 };
-use async_trait::async_trait;
 use serde_json::json;
 
-#[async_trait(?Send)]
 pub trait Group {
     #[doc(hidden)]
     fn id(&self) -> i64;
 
-    async fn update_shout(&mut self, message: String) -> RobloxResult<GroupShout> {
-        api_helper::patch(
-            format!("https://groups.roblox.com/v1/groups/{}/status", self.id()),
-            json!({ "message": message }),
-        )
-        .await
-        .map_async(api_helper::deserialize_body)
-        .await
+    fn update_shout<'a>(
+        &'a mut self,
+        client: &RobloxClient,
+        message: String,
+    ) -> impl Future<Output = RobloxResult<GroupShout>> + Send + 'a {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/status", self.id()),
+        );
+        async move {
+            api_helper::authenticated_patch(&client, url, json!({ "message": message }))
+                .await
+                .and_then(api_helper::deserialize_body)
+        }
     }
 
-    async fn accept_join_request(&self, user_id: i64) -> RobloxResult<()> {
-        api_helper::post(
-            format!(
-                "https://groups.roblox.com/v1/groups/{}/join-requests/users/#{}",
-                self.id(),
-                user_id
-            ),
-            json!({}),
-        )
-        .await
-        .map(|_| ())
+    fn accept_join_request(
+        &self,
+        client: &RobloxClient,
+        user_id: i64,
+    ) -> impl Future<Output = RobloxResult<()>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/join-requests/users/#{}", self.id(), user_id),
+        );
+        async move {
+            api_helper::authenticated_post(&client, url, json!({}))
+                .await
+                .map(|_| ())
+        }
     }
 
-    async fn decline_join_request(&self, user_id: i64) -> RobloxResult<()> {
-        api_helper::delete(format!(
-            "https://groups.roblox.com/v1/groups/{}/join-requests/users/{}",
-            self.id(),
-            user_id
-        ))
-        .await
-        .map(|_| ())
+    fn decline_join_request(
+        &self,
+        client: &RobloxClient,
+        user_id: i64,
+    ) -> impl Future<Output = RobloxResult<()>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/join-requests/users/{}", self.id(), user_id),
+        );
+        async move {
+            api_helper::authenticated_delete(&client, url)
+                .await
+                .map(|_| ())
+        }
     }
 
-    async fn kick(&self, user_id: i64) -> RobloxResult<()> {
-        api_helper::delete(format!(
-            "https://groups.roblox.com/v1/groups/{}/users/{}",
-            self.id(),
-            user_id
-        ))
-        .await
-        .map(|_| ())
+    fn kick(
+        &self,
+        client: &RobloxClient,
+        user_id: i64,
+    ) -> impl Future<Output = RobloxResult<()>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/users/{}", self.id(), user_id),
+        );
+        async move {
+            api_helper::authenticated_delete(&client, url)
+                .await
+                .map(|_| ())
+        }
     }
 
-    async fn roles(&self) -> RobloxResult<Vec<GroupRole>> {
-        api_helper::get(format!(
-            "https://groups.roblox.com/v1/groups/{}/roles",
-            self.id()
-        ))
-        .await
-        .map_async(api_helper::deserialize_body::<GroupRolesResponse>)
-        .await
-        .map(|data| data.roles)
+    fn roles(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<Vec<GroupRole>>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/roles", self.id()),
+        );
+        async move {
+            api_helper::get(&client, url)
+                .await
+                .and_then(api_helper::deserialize_body::<GroupRolesResponse>)
+                .map(|data| data.roles)
+        }
     }
 
-    async fn set_user_role(&self, user_id: i64, role_id: i64) -> RobloxResult<()> {
-        api_helper::patch(
-            format!(
-                "https://groups.roblox.com/v1/groups/{}/users/{}",
-                self.id(),
-                user_id
-            ),
-            json!({ "roleId": role_id }),
-        )
-        .await
-        .map(|_| ())
+    fn set_user_role(
+        &self,
+        client: &RobloxClient,
+        user_id: i64,
+        role_id: i64,
+    ) -> impl Future<Output = RobloxResult<()>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/users/{}", self.id(), user_id),
+        );
+        async move {
+            api_helper::authenticated_patch(&client, url, json!({ "roleId": role_id }))
+                .await
+                .map(|_| ())
+        }
     }
 
-    fn members(&self) -> PageIterator<Member, Member> {
+    fn members(&self, client: &RobloxClient) -> PageIterator<Member, Member> {
         PageIterator::new(
-            format!("https://groups.roblox.com/v1/groups/{}/users", self.id()),
+            client.clone(),
+            client.endpoint(
+                RobloxService::Groups,
+                &format!("/v1/groups/{}/users", self.id()),
+            ),
             identity_mapper,
         )
     }
 
-    async fn settings(&self) -> RobloxResult<GroupSettings> {
-        api_helper::get(format!(
-            "https://groups.roblox.com/v1/groups/{}/settings",
-            self.id()
-        ))
-        .await
-        .map_async(api_helper::deserialize_body)
-        .await
+    fn settings(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<GroupSettings>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/settings", self.id()),
+        );
+        async move {
+            api_helper::authenticated_get(&client, url)
+                .await
+                .and_then(api_helper::deserialize_body)
+        }
     }
 
-    async fn update_settings(
+    fn update_settings(
         &self,
+        client: &RobloxClient,
         is_approval_required: Option<bool>,
         are_enemies_allowed: Option<bool>,
         are_group_funds_visible: Option<bool>,
         are_group_games_visible: Option<bool>,
-    ) -> RobloxResult<()> {
-        api_helper::patch(
-            format!("https://groups.roblox.com/v1/groups/{}/settings", self.id()),
-            json!({
-                "isApprovalRequired": is_approval_required,
-                "areEnemiesAllowed": are_enemies_allowed,
-                "areGroupFundsVisible": are_group_funds_visible,
-                "areGroupGamesVisible": are_group_games_visible
-            }),
-        )
-        .await
-        .map(|_| ())
+    ) -> impl Future<Output = RobloxResult<()>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/settings", self.id()),
+        );
+        let body = json!({
+            "isApprovalRequired": is_approval_required,
+            "areEnemiesAllowed": are_enemies_allowed,
+            "areGroupFundsVisible": are_group_funds_visible,
+            "areGroupGamesVisible": are_group_games_visible
+        });
+        async move {
+            api_helper::authenticated_patch(&client, url, body)
+                .await
+                .map(|_| ())
+        }
     }
 
-    async fn delete_all_wall_posts_from_user(&self, user_id: i64) -> RobloxResult<()> {
-        api_helper::delete(format!(
-            "https://groups.roblox.com/v1/groups/{}/wall/users/{}/posts",
-            self.id(),
-            user_id
-        ))
-        .await
-        .map(|_| ())
+    fn delete_all_wall_posts_from_user(
+        &self,
+        client: &RobloxClient,
+        user_id: i64,
+    ) -> impl Future<Output = RobloxResult<()>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/wall/users/{}/posts", self.id(), user_id),
+        );
+        async move {
+            api_helper::authenticated_delete(&client, url)
+                .await
+                .map(|_| ())
+        }
     }
 
-    async fn delete_wall_post(&self, wall_post_id: i64) -> RobloxResult<()> {
-        api_helper::delete(format!(
-            "https://groups.roblox.com/v1/groups/{}/wall/posts/{}",
-            self.id(),
-            wall_post_id
-        ))
-        .await
-        .map(|_| ())
+    fn delete_wall_post(
+        &self,
+        client: &RobloxClient,
+        wall_post_id: i64,
+    ) -> impl Future<Output = RobloxResult<()>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/wall/posts/{}", self.id(), wall_post_id),
+        );
+        async move {
+            api_helper::authenticated_delete(&client, url)
+                .await
+                .map(|_| ())
+        }
     }
 
-    fn wall_posts(&self) -> PageIterator<WallPost, WallPost> {
+    fn wall_posts(&self, client: &RobloxClient) -> PageIterator<WallPost, WallPost> {
         PageIterator::new(
-            format!(
-                "https://groups.roblox.com/v2/groups/{}/wall/posts",
-                self.id()
+            client.clone(),
+            client.endpoint(
+                RobloxService::Groups,
+                &format!("/v2/groups/{}/wall/posts", self.id()),
             ),
             identity_mapper,
         )
     }
 
-    fn join_requests(&self) -> PageIterator<JoinRequest, JoinRequest> {
+    fn join_requests(&self, client: &RobloxClient) -> PageIterator<JoinRequest, JoinRequest> {
         PageIterator::new(
-            format!(
-                "https://groups.roblox.com/v1/groups/{}/join-requests",
-                self.id()
+            client.clone(),
+            client.endpoint(
+                RobloxService::Groups,
+                &format!("/v1/groups/{}/join-requests", self.id()),
             ),
             identity_mapper,
         )
+        .authenticated()
     }
 
-    async fn social_links(&self) -> RobloxResult<Vec<SocialLink>> {
-        api_helper::get(format!(
-            "https://groups.roblox.com/v1/groups/{}/social-links",
-            self.id()
-        ))
-        .await
-        .map_async(api_helper::deserialize_body::<ApiArrayResponse<SocialLink>>)
-        .await
-        .map(|data| data.data)
+    fn social_links(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<Vec<SocialLink>>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/social-links", self.id()),
+        );
+        async move {
+            api_helper::get(&client, url)
+                .await
+                .and_then(api_helper::deserialize_body::<ApiArrayResponse<SocialLink>>)
+                .map(|data| data.data)
+        }
     }
 
     // TODO get someone to test this
-    async fn join_request_from_user(&self, user_id: i64) -> RobloxResult<Option<JoinRequest>> {
-        api_helper::get(format!(
-            "https://groups.roblox.com/v1/groups/{}/join-requests/users/{}",
-            self.id(),
-            user_id
-        ))
-        .await
-        .map_async(|response| async {
-            // If the specified user doesn't have an outgoing join request for this group, the api returns status code 200 with body "null" :jak:
-            let text = response.text().await.unwrap();
-            if text == "null" {
-                None
-            } else {
-                Some(serde_json::from_str(&text).unwrap())
-            }
-        })
-        .await
+    fn join_request_from_user(
+        &self,
+        client: &RobloxClient,
+        user_id: i64,
+    ) -> impl Future<Output = RobloxResult<Option<JoinRequest>>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Groups,
+            &format!("/v1/groups/{}/join-requests/users/{}", self.id(), user_id),
+        );
+        async move {
+            api_helper::authenticated_get(&client, url)
+                .await
+                // If the specified user doesn't have an outgoing join request for this group, the api returns status code 200 with body "null" :jak:
+                // which deserializes to None
+                .and_then(api_helper::deserialize_body::<Option<JoinRequest>>)
+        }
     }
 }
@@ -1,28 +1,26 @@
 //! Traits that provide shared methods for structs
 //!
-//! Because of the `async_trait` crate, the definition of each function gets
-//! messy. The actual return of each function is after `Future<Output = ...>`.
+//! Methods that send requests return a future that is `Send + 'static` and
+//! holds its own clone of the client, so it can be spawned onto a
+//! multi-threaded runtime. The one exception is [GroupDerive::update_shout],
+//! whose future borrows the struct so that [Group](crate::models::Group) can
+//! update its shout field.
 //!
-//! As an example of interpretation:
+//! ```no_run
+//! use oxid_roblox::{derives::GroupDerive, RobloxClient};
 //!
+//! # async fn run(client: RobloxClient) {
+//! let group = oxid_roblox::base_group(1);
+//! let kicks = [1, 2, 3].map(|user_id| tokio::spawn(group.kick(&client, user_id)));
+//! for kick in kicks {
+//!     kick.await.unwrap().unwrap();
+//! }
+//! # }
 //! ```
-//! fn update_shout<'life0, 'async_trait>(
-//!     &'life0 mut self,
-//!     message: String
-//! ) -> Pin<Box<dyn Future<Output = RobloxResult<GroupShout>> + 'async_trait>>
-//! where
-//!     Self: 'async_trait,
-//!     'life0: 'async_trait
-//! ```
-//!
-//! Becomes this:
 //!
-//! ```
-//! async fn update_shout(
-//!     &mut self,
-//!     message: String
-//! ) -> RobloxResult<GroupShout>>
-//! ```
+//! Every method takes the [RobloxClient](crate::RobloxClient) that its
+//! requests should be sent with. To use the process-wide client, pass
+//! [default_client](crate::default_client).
 //!
 //! You can check out all the traits provided below. To see which structs
 //! implement each trait, check the "Implementors" section (at the bottom of
@@ -1,28 +1,37 @@
-use async_trait::async_trait;
+use std::future::Future;
+
 use serde_json::json;
 
-use crate::util::{api_helper, RobloxResult};
+use crate::{
+    util::{api_helper, RobloxResult, RobloxService},
+    RobloxClient,
+};
 
-#[async_trait(?Send)]
 pub trait Plugin {
     #[doc(hidden)]
     fn id(&self) -> i64;
 
-    async fn update(
+    fn update(
         &self,
+        client: &RobloxClient,
         name: Option<&str>,
         description: Option<&str>,
         comments_enabled: Option<bool>,
-    ) -> RobloxResult<()> {
-        api_helper::patch(
-            format!("https://develop.roblox.com/v1/plugins/{}", self.id()),
-            json!({
-                "name": name,
-                "description": description,
-                "commentsEnabled": comments_enabled,
-            }),
-        )
-        .await
-        .map(|_| ())
+    ) -> impl Future<Output = RobloxResult<()>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Develop,
+            &format!("/v1/plugins/{}", self.id()),
+        );
+        let body = json!({
+            "name": name,
+            "description": description,
+            "commentsEnabled": comments_enabled,
+        });
+        async move {
+            api_helper::authenticated_patch(&client, url, body)
+                .await
+                .map(|_| ())
+        }
     }
 }
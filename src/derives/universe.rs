@@ -1,4 +1,4 @@
-use async_trait::async_trait;
+use std::future::Future;
 
 use crate::{
     models::{Badge, GamePass, SocialLink, UniverseLiveStats},
@@ -6,64 +6,84 @@ use crate::{
         api_helper,
         paging::{identity_mapper, PageIterator},
         responses::{ApiArrayResponse, UniverseFavoriteCountResponse},
-        ResultExtensions, RobloxResult,
+        RobloxResult, RobloxService,
     },
+    RobloxClient,
 };
 
-#[async_trait(?Send)]
 pub trait Universe {
     #[doc(hidden)]
     fn id(&self) -> i64;
 
-    async fn favorite_count(&self) -> RobloxResult<i64> {
-        api_helper::get(format!(
-            "https://games.roblox.com/v1/games/{}/favorites/count",
-            self.id()
-        ))
-        .await
-        .map_async(api_helper::deserialize_body::<UniverseFavoriteCountResponse>)
-        .await
-        .map(|data| data.favorites_count)
+    fn favorite_count(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<i64>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Games,
+            &format!("/v1/games/{}/favorites/count", self.id()),
+        );
+        async move {
+            api_helper::get(&client, url)
+                .await
+                .and_then(api_helper::deserialize_body::<UniverseFavoriteCountResponse>)
+                .map(|data| data.favorites_count)
+        }
     }
 
-    fn badges(&self) -> PageIterator<Badge, Badge> {
+    fn badges(&self, client: &RobloxClient) -> PageIterator<Badge, Badge> {
         PageIterator::new(
-            format!(
-                "https://badges.roblox.com/v1/universes/{}/badges",
-                self.id()
+            client.clone(),
+            client.endpoint(
+                RobloxService::Badges,
+                &format!("/v1/universes/{}/badges", self.id()),
             ),
             identity_mapper,
         )
     }
 
-    async fn live_stats(&self) -> RobloxResult<UniverseLiveStats> {
-        api_helper::get(format!(
-            "https://develop.roblox.com/v1/universes/{}/live-stats",
-            self.id()
-        ))
-        .await
-        .map_async(api_helper::deserialize_body)
-        .await
+    fn live_stats(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<UniverseLiveStats>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Develop,
+            &format!("/v1/universes/{}/live-stats", self.id()),
+        );
+        async move {
+            api_helper::authenticated_get(&client, url)
+                .await
+                .and_then(api_helper::deserialize_body)
+        }
     }
 
-    fn gamepasses(&self) -> PageIterator<GamePass, GamePass> {
+    fn gamepasses(&self, client: &RobloxClient) -> PageIterator<GamePass, GamePass> {
         PageIterator::new(
-            format!(
-                "https://games.roblox.com/v1/games/{}/game-passes",
-                self.id()
+            client.clone(),
+            client.endpoint(
+                RobloxService::Games,
+                &format!("/v1/games/{}/game-passes", self.id()),
             ),
             identity_mapper,
         )
     }
 
-    async fn social_links(&self) -> RobloxResult<Vec<SocialLink>> {
-        api_helper::get(format!(
-            "https://games.roblox.com/v1/games/{}/social-links/list",
-            self.id()
-        ))
-        .await
-        .map_async(api_helper::deserialize_body::<ApiArrayResponse<SocialLink>>)
-        .await
-        .map(|data| data.data)
+    fn social_links(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<Vec<SocialLink>>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Games,
+            &format!("/v1/games/{}/social-links/list", self.id()),
+        );
+        async move {
+            api_helper::get(&client, url)
+                .await
+                .and_then(api_helper::deserialize_body::<ApiArrayResponse<SocialLink>>)
+                .map(|data| data.data)
+        }
     }
 }
@@ -1,69 +1,101 @@
-use crate::util::{
-    api_helper,
-    paging::PageIterator,
-    responses::{CountResponse, CurrencyResponse, UsernameHistoryResponse},
-    ResultExtensions, RobloxResult,
+use std::future::Future;
+
+use crate::{
+    util::{
+        api_helper,
+        paging::PageIterator,
+        responses::{CountResponse, CurrencyResponse, UsernameHistoryResponse},
+        RobloxResult, RobloxService,
+    },
+    RobloxClient,
 };
-use async_trait::async_trait;
 
-async fn get_generic_count(user_id: i64, channel: &str) -> RobloxResult<i32> {
-    api_helper::get(format!(
-        "https://friends.roblox.com/v1/users/{}/{}/count",
-        user_id, channel
-    ))
-    .await
-    .map_async(api_helper::deserialize_body::<CountResponse>)
+async fn get_generic_count(
+    client: RobloxClient,
+    user_id: i64,
+    channel: &'static str,
+) -> RobloxResult<i32> {
+    api_helper::get(
+        &client,
+        client.endpoint(
+            RobloxService::Friends,
+            &format!("/v1/users/{}/{}/count", user_id, channel),
+        ),
+    )
     .await
+    .and_then(api_helper::deserialize_body::<CountResponse>)
     .map(|data| data.count)
 }
 
-#[async_trait(?Send)]
 pub trait User {
     #[doc(hidden)]
     fn id(&self) -> i64;
 
-    async fn currency(&self) -> RobloxResult<i64> {
-        api_helper::get(format!(
-            "https://economy.roblox.com/v1/users/{}/currency",
-            self.id()
-        ))
-        .await
-        .map_async(api_helper::deserialize_body::<CurrencyResponse>)
-        .await
-        .map(|data| data.robux)
+    fn currency(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<i64>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::Economy,
+            &format!("/v1/users/{}/currency", self.id()),
+        );
+        async move {
+            api_helper::authenticated_get(&client, url)
+                .await
+                .and_then(api_helper::deserialize_body::<CurrencyResponse>)
+                .map(|data| data.robux)
+        }
     }
 
-    async fn has_premium(&self) -> bool {
-        api_helper::deserialize_body(
-            api_helper::get(format!(
-                "https://premiumfeatures.roblox.com/v1/users/{}/validate-membership",
-                self.id()
-            ))
-            .await
-            .unwrap(),
-        )
-        .await
+    fn has_premium(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<bool>> + Send + 'static {
+        let client = client.clone();
+        let url = client.endpoint(
+            RobloxService::PremiumFeatures,
+            &format!("/v1/users/{}/validate-membership", self.id()),
+        );
+        async move {
+            api_helper::authenticated_get(&client, url)
+                .await
+                .and_then(api_helper::deserialize_body)
+        }
     }
 
-    async fn friend_count(&self) -> RobloxResult<i32> {
-        get_generic_count(self.id(), "friends").await
+    fn friend_count(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<i32>> + Send + 'static {
+        get_generic_count(client.clone(), self.id(), "friends")
     }
 
-    async fn follower_count(&self) -> RobloxResult<i32> {
-        get_generic_count(self.id(), "followers").await
+    fn follower_count(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<i32>> + Send + 'static {
+        get_generic_count(client.clone(), self.id(), "followers")
     }
 
-    async fn following_count(&self) -> RobloxResult<i32> {
-        get_generic_count(self.id(), "followings").await
+    fn following_count(
+        &self,
+        client: &RobloxClient,
+    ) -> impl Future<Output = RobloxResult<i32>> + Send + 'static {
+        get_generic_count(client.clone(), self.id(), "followings")
     }
 
-    fn username_history(&self) -> PageIterator<UsernameHistoryResponse, String> {
+    fn username_history(
+        &self,
+        client: &RobloxClient,
+    ) -> PageIterator<UsernameHistoryResponse, String> {
         PageIterator::new(
-            format!(
-                "https://users.roblox.com/v1/users/{}/username-history",
-                self.id()
+            client.clone(),
+            client.endpoint(
+                RobloxService::Users,
+                &format!("/v1/users/{}/username-history", self.id()),
             ),
-            |data| data.name.clone(),
+            |data: &UsernameHistoryResponse| data.name.clone(),
         )
     }
 }
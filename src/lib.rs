@@ -1,9 +1,13 @@
 #![doc = include_str!("../README.md")]
 
 pub mod bases;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod client;
 pub mod derives;
 pub mod models;
+mod roblox_client;
 pub mod util;
 
 pub use client::*;
+pub use roblox_client::*;
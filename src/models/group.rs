@@ -1,5 +1,6 @@
-use crate::{derives::GroupDerive, util::RobloxResult};
-use async_trait::async_trait;
+use std::future::Future;
+
+use crate::{bases::BaseGroup, derives::GroupDerive, util::RobloxResult, RobloxClient};
 use serde::Deserialize;
 
 use super::{GroupShout, SkinnyUser};
@@ -20,16 +21,25 @@ pub struct Group {
     pub has_verified_badge: bool,
 }
 
-#[async_trait(?Send)]
 impl GroupDerive for Group {
     fn id(&self) -> i64 {
         self.id
     }
 
     /// A version of [`GroupDerive::update_shout`] that updates the shout field of this group.
-    async fn update_shout(&mut self, message: String) -> RobloxResult<GroupShout> {
-        let shout = self.update_shout(message).await?;
-        self.shout = Some(shout.clone());
-        Ok(shout)
+    fn update_shout<'a>(
+        &'a mut self,
+        client: &RobloxClient,
+        message: String,
+    ) -> impl Future<Output = RobloxResult<GroupShout>> + Send + 'a {
+        let client = client.clone();
+        async move {
+            // Calling self.update_shout here would recurse into this method, so go through the base instead
+            let shout = BaseGroup { id: self.id }
+                .update_shout(&client, message)
+                .await?;
+            self.shout = Some(shout.clone());
+            Ok(shout)
+        }
     }
 }
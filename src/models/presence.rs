@@ -2,8 +2,9 @@ use crate::{
     bases::BaseUniverse,
     util::{
         api_helper, parsers::parse_optional_base_universe, responses::PresenceLastOnlineResponse,
-        ResultExtensions, RobloxResult,
+        RobloxResult, RobloxService,
     },
+    RobloxClient,
 };
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
@@ -35,22 +36,20 @@ pub struct Presence {
 }
 
 impl Presence {
-    pub async fn last_online(&self) -> RobloxResult<DateTime<Utc>> {
+    pub async fn last_online(&self, client: &RobloxClient) -> RobloxResult<Option<DateTime<Utc>>> {
         api_helper::post(
-            "https://presence.roblox.com/v1/presence/last-online".to_owned(),
+            client,
+            client.endpoint(RobloxService::Presence, "/v1/presence/last-online"),
             json!({
                 "userIds": [self.user_id]
             }),
         )
         .await
-        .map_async(api_helper::deserialize_body::<PresenceLastOnlineResponse>)
-        .await
+        .and_then(api_helper::deserialize_body::<PresenceLastOnlineResponse>)
         .map(|data| {
             data.last_online_timestamps
                 .first()
-                .cloned()
-                .unwrap()
-                .last_online
+                .map(|timestamp| timestamp.last_online)
         })
     }
 }
@@ -0,0 +1,669 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, InvalidHeaderValue, AUTHORIZATION, SET_COOKIE},
+    Client,
+};
+
+use crate::{
+    models::{Presence, SkinnyUser, Universe},
+    util::{
+        cache::{CacheConfig, ResponseCache},
+        challenge::ChallengeHandler,
+        csrf::CsrfManager,
+        loader::Loader,
+        metrics::MetricsHook,
+        middleware::Middleware,
+        runtime::{Runtime, TokioRuntime},
+        telemetry,
+        transport::{ReqwestTransport, Transport},
+        Credential, RetryPolicy, RobloxResult, RobloxService,
+    },
+};
+
+// Called with the new value of a rotated .ROBLOSECURITY cookie
+type RoblosecurityCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+lazy_static! {
+    static ref DEFAULT_CLIENT: RobloxClient = RobloxClient::new();
+}
+
+/// Returns the process-wide client used by the free functions of this crate,
+/// such as [user_from_id](crate::user_from_id) and [set_roblosecurity](crate::set_roblosecurity).
+pub fn default_client() -> &'static RobloxClient {
+    &DEFAULT_CLIENT
+}
+
+/// A client for the Roblox web API.
+///
+/// Each client owns its own [Transport](crate::util::transport::Transport), .ROBLOSECURITY cookie and
+/// x-csrf-token, so several accounts can be used side by side in the same
+/// process:
+///
+/// ```no_run
+/// # async fn run() {
+/// use oxid_roblox::RobloxClient;
+///
+/// let first_bot = RobloxClient::builder().roblosecurity("first cookie").build();
+/// let second_bot = RobloxClient::builder().roblosecurity("second cookie").build();
+///
+/// println!("{:?}", first_bot.authenticated_user().await);
+/// println!("{:?}", second_bot.authenticated_user().await);
+/// # }
+/// ```
+///
+/// Cloning a client is cheap, and the clone shares its session with the original.
+#[derive(Clone)]
+pub struct RobloxClient {
+    inner: Arc<ClientInner>,
+}
+
+struct ClientInner {
+    transport: Arc<dyn Transport>,
+    runtime: Arc<dyn Runtime>,
+    default_headers: HeaderMap,
+    base_urls: HashMap<RobloxService, String>,
+    retry_policy: RetryPolicy,
+    cache: Option<ResponseCache>,
+    multi_get_concurrency: usize,
+    metrics_hook: Option<Arc<dyn MetricsHook>>,
+    challenge_handler: Option<Arc<dyn ChallengeHandler>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    user_loader: Loader<SkinnyUser>,
+    user_presence_loader: Loader<Presence>,
+    universe_loader: Loader<Universe>,
+    roblosecurity: RwLock<Option<String>>,
+    api_key: RwLock<Option<String>>,
+    access_token: RwLock<Option<String>>,
+    on_roblosecurity_rotated: Option<RoblosecurityCallback>,
+    csrf: CsrfManager,
+}
+
+// The value of the .ROBLOSECURITY cookie set by a Set-Cookie header, unless the header deletes
+// it or comes from a redacted cassette
+fn roblosecurity_from_set_cookie(set_cookie: &str) -> Option<&str> {
+    let (name, value) = set_cookie.split(';').next()?.trim().split_once('=')?;
+    (name == ".ROBLOSECURITY" && !value.is_empty() && value != "[REDACTED]").then_some(value)
+}
+
+impl RobloxClient {
+    /// Creates an unauthenticated client with the default settings.
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    pub fn builder() -> RobloxClientBuilder {
+        RobloxClientBuilder::new()
+    }
+
+    /// Sets the .ROBLOSECURITY cookie used to authenticate the requests sent by this client
+    /// (and every clone of it).
+    pub fn set_roblosecurity(&self, roblosecurity: &str) {
+        *self.inner.roblosecurity.write().unwrap() = Some(roblosecurity.to_owned());
+    }
+
+    // Picks up a .ROBLOSECURITY cookie rotated by Roblox through the Set-Cookie headers of a response
+    pub(crate) fn update_roblosecurity(&self, headers: &HeaderMap) {
+        let Some(roblosecurity) = headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .find_map(roblosecurity_from_set_cookie)
+        else {
+            return;
+        };
+
+        {
+            let mut current = self.inner.roblosecurity.write().unwrap();
+            if current.as_deref() == Some(roblosecurity) {
+                return;
+            }
+            *current = Some(roblosecurity.to_owned());
+        }
+
+        telemetry::roblosecurity_rotated();
+        if let Some(on_roblosecurity_rotated) = &self.inner.on_roblosecurity_rotated {
+            on_roblosecurity_rotated(roblosecurity);
+        }
+    }
+
+    /// Sets the Open Cloud API key used to authenticate the requests sent by this client
+    /// (and every clone of it) to [RobloxService::Apis]. See [Credential].
+    pub fn set_api_key(&self, api_key: &str) {
+        *self.inner.api_key.write().unwrap() = Some(api_key.to_owned());
+    }
+
+    /// Sets the OAuth 2.0 access token used to authenticate the requests sent by this client
+    /// (and every clone of it) to [RobloxService::Apis] when it has no API key, e.g. after
+    /// [refreshing](crate::util::oauth::OAuthApp::refresh) it. See [Credential].
+    pub fn set_access_token(&self, access_token: &str) {
+        *self.inner.access_token.write().unwrap() = Some(access_token.to_owned());
+    }
+
+    /// The base URL requests to `service` are sent to, without a trailing slash.
+    pub fn base_url(&self, service: RobloxService) -> &str {
+        &self.inner.base_urls[&service]
+    }
+
+    // Builds the full URL of an endpoint from its path, e.g. "/v1/users/1"
+    pub(crate) fn endpoint(&self, service: RobloxService, path: &str) -> String {
+        format!("{}{}", self.base_url(service), path)
+    }
+
+    // Splits a URL into the service it is sent to and its path
+    pub(crate) fn service_of<'a>(&self, url: &'a str) -> Option<(RobloxService, &'a str)> {
+        self.inner
+            .base_urls
+            .iter()
+            .filter_map(|(service, base_url)| {
+                let path = url.strip_prefix(base_url.as_str())?;
+                (path.is_empty() || path.starts_with(['/', '?'])).then_some((
+                    *service,
+                    path,
+                    base_url.len(),
+                ))
+            })
+            .max_by_key(|(_, _, base_url_length)| *base_url_length)
+            .map(|(service, path, _)| (service, path))
+    }
+
+    /// The response cache of this client, if it was built with one.
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.inner.cache.as_ref()
+    }
+
+    /// Looks up a user through a batch shared with every other `load_user` call made within
+    /// the [batch window](RobloxClientBuilder::batch_window), which is sent as one
+    /// [users_from_ids](RobloxClient::users_from_ids) request. Concurrent lookups of the
+    /// same user share one result.
+    ///
+    /// ```no_run
+    /// # async fn run() {
+    /// use futures_util::future::join_all;
+    /// use oxid_roblox::RobloxClient;
+    ///
+    /// let client = RobloxClient::new();
+    /// // A single request
+    /// let users = join_all((1..=100).map(|user_id| client.load_user(user_id))).await;
+    /// # }
+    /// ```
+    pub async fn load_user(&self, user_id: i64) -> RobloxResult<Option<SkinnyUser>> {
+        self.inner.user_loader.load(self, user_id).await
+    }
+
+    /// Like [RobloxClient::load_user], batched into
+    /// [user_presences_from_ids](RobloxClient::user_presences_from_ids) requests.
+    pub async fn load_user_presence(&self, user_id: i64) -> RobloxResult<Option<Presence>> {
+        self.inner.user_presence_loader.load(self, user_id).await
+    }
+
+    /// Like [RobloxClient::load_user], batched into
+    /// [universes_from_ids](RobloxClient::universes_from_ids) requests.
+    pub async fn load_universe(&self, universe_id: i64) -> RobloxResult<Option<Universe>> {
+        self.inner.universe_loader.load(self, universe_id).await
+    }
+
+    pub(crate) fn multi_get_concurrency(&self) -> usize {
+        self.inner.multi_get_concurrency
+    }
+
+    pub(crate) fn metrics_hook(&self) -> Option<&dyn MetricsHook> {
+        self.inner.metrics_hook.as_deref()
+    }
+
+    pub(crate) fn challenge_handler(&self) -> Option<&dyn ChallengeHandler> {
+        self.inner.challenge_handler.as_deref()
+    }
+
+    pub(crate) fn middlewares(&self) -> &[Arc<dyn Middleware>] {
+        &self.inner.middlewares
+    }
+
+    pub(crate) fn retry_policy(&self) -> &RetryPolicy {
+        &self.inner.retry_policy
+    }
+
+    pub(crate) fn transport(&self) -> &dyn Transport {
+        self.inner.transport.as_ref()
+    }
+
+    pub(crate) fn runtime(&self) -> &dyn Runtime {
+        self.inner.runtime.as_ref()
+    }
+
+    pub(crate) fn csrf(&self) -> &CsrfManager {
+        &self.inner.csrf
+    }
+
+    // Builds the headers for a request from the current state of the session. The locks are
+    // released before returning so that no request holds them across an await point
+    pub(crate) fn headers(&self, credential: Option<Credential>) -> HeaderMap {
+        let mut headers = self.inner.default_headers.clone();
+
+        match credential {
+            None => {}
+            Some(Credential::Roblosecurity) => {
+                // A cookie that is not a valid header value is left out, so the request fails
+                // with RobloxError::Unauthorized instead of panicking
+                if let Some(Ok(mut cookie)) =
+                    self.inner
+                        .roblosecurity
+                        .read()
+                        .unwrap()
+                        .as_ref()
+                        .map(|roblosecurity| {
+                            HeaderValue::from_str(&format!(".ROBLOSECURITY={};", roblosecurity))
+                        })
+                {
+                    cookie.set_sensitive(true);
+                    headers.insert("Cookie", cookie);
+                }
+
+                if let Some(csrf_token) = self.inner.csrf.token() {
+                    headers.insert("x-csrf-token", csrf_token);
+                }
+            }
+            Some(Credential::ApiKey) => {
+                if let Some(Ok(mut api_key)) = self
+                    .inner
+                    .api_key
+                    .read()
+                    .unwrap()
+                    .as_deref()
+                    .map(HeaderValue::from_str)
+                {
+                    api_key.set_sensitive(true);
+                    headers.insert("x-api-key", api_key);
+                }
+            }
+            Some(Credential::OAuth) => {
+                if let Some(Ok(mut authorization)) = self
+                    .inner
+                    .access_token
+                    .read()
+                    .unwrap()
+                    .as_ref()
+                    .map(|access_token| HeaderValue::from_str(&format!("Bearer {}", access_token)))
+                {
+                    authorization.set_sensitive(true);
+                    headers.insert(AUTHORIZATION, authorization);
+                }
+            }
+        }
+
+        headers
+    }
+
+    pub(crate) fn has_credential(&self, credential: Credential) -> bool {
+        match credential {
+            Credential::Roblosecurity => self.inner.roblosecurity.read().unwrap().is_some(),
+            Credential::ApiKey => self.inner.api_key.read().unwrap().is_some(),
+            Credential::OAuth => self.inner.access_token.read().unwrap().is_some(),
+        }
+    }
+
+    // Identifies the current value of `credential` without keeping it, so that the responses
+    // of different sessions are cached apart
+    pub(crate) fn session_of(&self, credential: Option<Credential>) -> Option<u64> {
+        let credential = credential?;
+        let value = match credential {
+            Credential::Roblosecurity => self.inner.roblosecurity.read().unwrap().clone(),
+            Credential::ApiKey => self.inner.api_key.read().unwrap().clone(),
+            Credential::OAuth => self.inner.access_token.read().unwrap().clone(),
+        }?;
+
+        let mut hasher = DefaultHasher::new();
+        (credential, value).hash(&mut hasher);
+        Some(hasher.finish())
+    }
+
+    // The credential a request to `service` is sent with when none was picked, and whether
+    // the request needs it. Requests to URLs outside of the services of the client are sent
+    // without any
+    pub(crate) fn default_credential(
+        &self,
+        service: Option<RobloxService>,
+    ) -> Option<(Credential, bool)> {
+        match service? {
+            RobloxService::Apis
+                if !self.has_credential(Credential::ApiKey)
+                    && self.has_credential(Credential::OAuth) =>
+            {
+                Some((Credential::OAuth, true))
+            }
+            RobloxService::Apis => Some((Credential::ApiKey, true)),
+            _ => Some((Credential::Roblosecurity, false)),
+        }
+    }
+}
+
+impl Default for RobloxClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A builder for [RobloxClient]. See [RobloxClient::builder].
+pub struct RobloxClientBuilder {
+    transport: Option<Arc<dyn Transport>>,
+    runtime: Option<Arc<dyn Runtime>>,
+    user_agent: HeaderValue,
+    roblosecurity: Option<String>,
+    api_key: Option<String>,
+    access_token: Option<String>,
+    scheme: String,
+    base_domain: String,
+    service_base_urls: HashMap<RobloxService, String>,
+    retry_policy: RetryPolicy,
+    cache: Option<CacheConfig>,
+    multi_get_concurrency: usize,
+    batch_window: Duration,
+    metrics_hook: Option<Arc<dyn MetricsHook>>,
+    challenge_handler: Option<Arc<dyn ChallengeHandler>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    on_roblosecurity_rotated: Option<RoblosecurityCallback>,
+}
+
+impl RobloxClientBuilder {
+    fn new() -> Self {
+        Self {
+            transport: None,
+            runtime: None,
+            user_agent: HeaderValue::from_static("Roblox/WinInet"),
+            roblosecurity: None,
+            api_key: None,
+            access_token: None,
+            scheme: "https".to_owned(),
+            base_domain: "roblox.com".to_owned(),
+            service_base_urls: HashMap::new(),
+            retry_policy: RetryPolicy::default(),
+            cache: None,
+            multi_get_concurrency: 4,
+            batch_window: Duration::from_millis(5),
+            metrics_hook: None,
+            challenge_handler: None,
+            middlewares: Vec::new(),
+            on_roblosecurity_rotated: None,
+        }
+    }
+
+    /// Sends requests through an existing [reqwest::Client] instead of creating a new one.
+    pub fn http_client(self, http_client: Client) -> Self {
+        self.transport(ReqwestTransport::from(http_client))
+    }
+
+    /// Sends requests through `transport` instead of [ReqwestTransport].
+    pub fn transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Sleeps and spawns background tasks with `runtime` instead of [TokioRuntime]. See
+    /// [runtime](crate::util::runtime).
+    pub fn runtime<R: Runtime + 'static>(mut self, runtime: R) -> Self {
+        self.runtime = Some(Arc::new(runtime));
+        self
+    }
+
+    /// Sends `user_agent` as the User-Agent header. A user agent that is not a valid header
+    /// value is ignored, keeping the previous one. See [RobloxClientBuilder::try_user_agent]
+    /// to be told about it instead.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        if let Ok(user_agent) = HeaderValue::from_str(user_agent) {
+            self.user_agent = user_agent;
+        }
+        self
+    }
+
+    /// Like [RobloxClientBuilder::user_agent], but fails on a user agent that is not a valid
+    /// header value, e.g. one with a line break.
+    pub fn try_user_agent(mut self, user_agent: &str) -> Result<Self, InvalidHeaderValue> {
+        self.user_agent = HeaderValue::from_str(user_agent)?;
+        Ok(self)
+    }
+
+    pub fn roblosecurity(mut self, roblosecurity: &str) -> Self {
+        self.roblosecurity = Some(roblosecurity.to_owned());
+        self
+    }
+
+    /// Authenticates the requests to [RobloxService::Apis] with an Open Cloud API key. See
+    /// [Credential].
+    pub fn api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_owned());
+        self
+    }
+
+    /// Authenticates the requests to [RobloxService::Apis] with an OAuth 2.0 access token when
+    /// no API key is set. See [oauth](crate::util::oauth).
+    pub fn access_token(mut self, access_token: &str) -> Self {
+        self.access_token = Some(access_token.to_owned());
+        self
+    }
+
+    /// Calls `callback` with the new .ROBLOSECURITY cookie whenever Roblox rotates it. The
+    /// client switches to the new cookie on its own, the callback is only needed to persist it:
+    ///
+    /// ```no_run
+    /// use oxid_roblox::RobloxClient;
+    ///
+    /// let client = RobloxClient::builder()
+    ///     .roblosecurity(&std::fs::read_to_string("cookie.txt").unwrap())
+    ///     .on_roblosecurity_rotated(|roblosecurity| {
+    ///         std::fs::write("cookie.txt", roblosecurity).unwrap();
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn on_roblosecurity_rotated<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_roblosecurity_rotated = Some(Arc::new(callback));
+        self
+    }
+
+    /// Sets the scheme of the base URL of every service, `https` by default.
+    pub fn scheme(mut self, scheme: &str) -> Self {
+        self.scheme = scheme.to_owned();
+        self
+    }
+
+    /// Sets the domain every service is a subdomain of, `roblox.com` by default. For example,
+    /// `.scheme("http").base_domain("roblox.test:8080")` sends the requests of
+    /// [RobloxService::Users] to `http://users.roblox.test:8080`.
+    pub fn base_domain(mut self, base_domain: &str) -> Self {
+        self.base_domain = base_domain.to_owned();
+        self
+    }
+
+    /// Sends the requests of `service` to `base_url` instead, ignoring the scheme and base
+    /// domain. This is useful to point the client at a local mock server:
+    ///
+    /// ```
+    /// use oxid_roblox::{util::RobloxService, RobloxClient};
+    ///
+    /// let client = RobloxService::ALL
+    ///     .into_iter()
+    ///     .fold(RobloxClient::builder(), |builder, service| {
+    ///         builder.service_base_url(
+    ///             service,
+    ///             &format!("http://127.0.0.1:8080/{}", service.subdomain()),
+    ///         )
+    ///     })
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     client.base_url(RobloxService::Users),
+    ///     "http://127.0.0.1:8080/users"
+    /// );
+    /// ```
+    pub fn service_base_url(mut self, service: RobloxService, base_url: &str) -> Self {
+        self.service_base_urls
+            .insert(service, base_url.trim_end_matches('/').to_owned());
+        self
+    }
+
+    /// Sets how failed requests are retried. See [RetryPolicy] for the default behaviour.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Caches the responses of GET requests. See [cache](crate::util::cache).
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// How many requests a multi-get such as [RobloxClient::users_from_ids] sends at the same
+    /// time when it has more ids than Roblox accepts in one request. 4 by default.
+    pub fn multi_get_concurrency(mut self, multi_get_concurrency: usize) -> Self {
+        self.multi_get_concurrency = multi_get_concurrency.max(1);
+        self
+    }
+
+    /// How long [RobloxClient::load_user] and the other loaders wait for more lookups before
+    /// sending a batch. 5ms by default.
+    pub fn batch_window(mut self, batch_window: Duration) -> Self {
+        self.batch_window = batch_window;
+        self
+    }
+
+    /// Calls `metrics_hook` after every request. See [metrics](crate::util::metrics).
+    pub fn metrics_hook<H: MetricsHook + 'static>(mut self, metrics_hook: H) -> Self {
+        self.metrics_hook = Some(Arc::new(metrics_hook));
+        self
+    }
+
+    /// Passes the challenges Roblox answers requests with to `challenge_handler`, and replays
+    /// the requests once they are solved. See [challenge](crate::util::challenge).
+    pub fn challenge_handler<H: ChallengeHandler + 'static>(
+        mut self,
+        challenge_handler: H,
+    ) -> Self {
+        self.challenge_handler = Some(Arc::new(challenge_handler));
+        self
+    }
+
+    /// Adds `middleware` after the ones already added, so that it sees requests after them
+    /// and responses before them. See [middleware](crate::util::middleware).
+    pub fn middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    pub fn build(self) -> RobloxClient {
+        let mut default_headers = HeaderMap::new();
+        default_headers.insert("User-Agent", self.user_agent);
+        default_headers.insert("Referer", "www.roblox.com".parse().unwrap());
+
+        let base_urls = RobloxService::ALL
+            .into_iter()
+            .map(|service| {
+                let base_url = self
+                    .service_base_urls
+                    .get(&service)
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        format!(
+                            "{}://{}.{}",
+                            self.scheme,
+                            service.subdomain(),
+                            self.base_domain
+                        )
+                    });
+                (service, base_url)
+            })
+            .collect();
+
+        RobloxClient {
+            inner: Arc::new(ClientInner {
+                transport: self
+                    .transport
+                    .unwrap_or_else(|| Arc::new(ReqwestTransport::new())),
+                runtime: self.runtime.unwrap_or_else(|| Arc::new(TokioRuntime)),
+                default_headers,
+                base_urls,
+                retry_policy: self.retry_policy,
+                cache: self.cache.map(ResponseCache::new),
+                multi_get_concurrency: self.multi_get_concurrency,
+                metrics_hook: self.metrics_hook,
+                challenge_handler: self.challenge_handler,
+                middlewares: self.middlewares,
+                user_loader: Loader::new(self.batch_window, |client, user_ids| {
+                    Box::pin(async move { client.users_from_ids_keyed(user_ids, false).await })
+                }),
+                user_presence_loader: Loader::new(self.batch_window, |client, user_ids| {
+                    Box::pin(async move { client.user_presences_from_ids_keyed(user_ids).await })
+                }),
+                universe_loader: Loader::new(self.batch_window, |client, universe_ids| {
+                    Box::pin(async move { client.universes_from_ids_keyed(universe_ids).await })
+                }),
+                roblosecurity: RwLock::new(self.roblosecurity),
+                api_key: RwLock::new(self.api_key),
+                access_token: RwLock::new(self.access_token),
+                on_roblosecurity_rotated: self.on_roblosecurity_rotated,
+                csrf: CsrfManager::new(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_roblosecurity_of_a_set_cookie() {
+        assert_eq!(
+            roblosecurity_from_set_cookie(
+                ".ROBLOSECURITY=_|WARNING|_abc; domain=.roblox.com; path=/; secure; HttpOnly"
+            ),
+            Some("_|WARNING|_abc")
+        );
+        assert_eq!(
+            roblosecurity_from_set_cookie(" .ROBLOSECURITY=abc"),
+            Some("abc")
+        );
+    }
+
+    #[test]
+    fn ignores_other_cookies_deletions_and_redactions() {
+        assert_eq!(
+            roblosecurity_from_set_cookie("RBXEventTrackerV2=abc; path=/"),
+            None
+        );
+        assert_eq!(
+            roblosecurity_from_set_cookie(".ROBLOSECURITY=; expires=Thu, 01 Jan 1970 00:00:00 GMT"),
+            None
+        );
+        assert_eq!(
+            roblosecurity_from_set_cookie(".ROBLOSECURITY=[REDACTED]"),
+            None
+        );
+        assert_eq!(
+            roblosecurity_from_set_cookie("path=/; .ROBLOSECURITY=abc"),
+            None
+        );
+        assert_eq!(roblosecurity_from_set_cookie(""), None);
+    }
+
+    #[test]
+    fn validates_user_agents() {
+        let builder = RobloxClient::builder().user_agent("bot/1.0");
+        assert_eq!(builder.user_agent, "bot/1.0");
+        let builder = builder.user_agent("bot\n2.0");
+        assert_eq!(builder.user_agent, "bot/1.0");
+        builder.build();
+
+        assert!(RobloxClient::builder().try_user_agent("bot\n2.0").is_err());
+        let builder = RobloxClient::builder().try_user_agent("bot/2.0").unwrap();
+        assert_eq!(builder.user_agent, "bot/2.0");
+    }
+}
@@ -1,88 +1,806 @@
-use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use lazy_static::lazy_static;
-use reqwest::{header::HeaderMap, Client, Method, Response, StatusCode};
+use chrono::{DateTime, Utc};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    Method, StatusCode,
+};
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
-use super::{get_api_errors_from_response, RobloxResult};
+use crate::RobloxClient;
 
-lazy_static! {
-    static ref HTTP_CLIENT: Client = Client::new();
-    static ref HEADERS: Arc<Mutex<HeaderMap>> = Arc::new(Mutex::new({
-        let mut headers = HeaderMap::new();
-        headers.insert("User-Agent", "Roblox/WinInet".parse().unwrap());
-        headers.insert("Referer", "www.roblox.com".parse().unwrap());
-        headers
-    }));
-}
-
-pub(crate) fn set_roblosecurity(roblosecurity: &str) {
-    HEADERS.clone().lock().unwrap().insert(
-        "Cookie",
-        format!(".ROBLOSECURITY={};", roblosecurity)
-            .parse()
-            .unwrap(),
-    );
+use super::{
+    challenge::{self, Challenge, ChallengeSolution},
+    csrf::CsrfManager,
+    get_error_from_response, get_oauth_error_from_response,
+    metrics::{RequestMetrics, StatusClass},
+    middleware::Next,
+    telemetry::{self, RequestSpan},
+    transport::{redact_body, Request, Response},
+    Credential, RobloxError, RobloxResult, RobloxService,
+};
+
+// How much of a body is kept in a RobloxError::Deserialization
+const BODY_EXCERPT_LENGTH: usize = 256;
+
+// The start of a body for a RobloxError::Deserialization, without the secrets it may hold,
+// such as the tokens of an OAuth response
+pub(crate) fn body_excerpt(body: &[u8]) -> String {
+    redact_body(body)
+        .chars()
+        .take(BODY_EXCERPT_LENGTH)
+        .collect()
 }
 
-pub(crate) async fn deserialize_body<T: DeserializeOwned>(response: Response) -> T {
-    response.json::<T>().await.unwrap()
+// A query string or form body of `pairs`
+pub(crate) fn urlencode<K: AsRef<str>, V: AsRef<str>>(pairs: &[(K, V)]) -> String {
+    let pairs: Vec<_> = pairs
+        .iter()
+        .map(|(key, value)| (key.as_ref(), value.as_ref()))
+        .collect();
+    // Serializing a list of string pairs can't fail
+    serde_urlencoded::to_string(pairs).unwrap_or_default()
 }
 
-async fn request(verb: Method, url: String, body: Option<Value>) -> RobloxResult<Response> {
-    let arc_ref = HEADERS.clone();
-    let mut headers = arc_ref.lock().unwrap();
+pub(crate) fn deserialize_body<T: DeserializeOwned>(response: Response) -> RobloxResult<T> {
+    serde_json::from_slice(&response.body).map_err(|error| RobloxError::Deserialization {
+        message: error.to_string(),
+        body_excerpt: body_excerpt(&response.body),
+    })
+}
 
-    let response = HTTP_CLIENT
-        .request(verb.clone(), url.clone())
-        .headers(headers.clone())
-        .json(&body)
-        .send()
-        .await
-        .unwrap();
+// The Retry-After header is either a number of seconds or an HTTP date
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get("retry-after")?.to_str().ok()?;
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => DateTime::parse_from_rfc2822(value)
+            .ok()
+            .and_then(|date| (date.with_timezone(&Utc) - Utc::now()).to_std().ok()),
+    }
+}
+
+fn is_token_validation_failure(error: &RobloxError) -> bool {
+    matches!(
+        error,
+        RobloxError::Api { status: StatusCode::FORBIDDEN, errors }
+            if errors.iter().any(|error| error.code == 0)
+    )
+}
+
+// How the body of an error response is read
+#[derive(Clone, Copy)]
+pub(crate) enum ErrorFormat {
+    // A list of errors, as returned by most Roblox APIs
+    Api,
+    // An error code and description, as returned by OAuth 2.0 endpoints
+    OAuth,
+}
+
+impl ErrorFormat {
+    fn error_of(self, response: &Response) -> Option<RobloxError> {
+        match self {
+            ErrorFormat::Api => None,
+            ErrorFormat::OAuth => get_oauth_error_from_response(response),
+        }
+    }
+}
+
+fn check_status(response: Response, request: &PendingRequest) -> RobloxResult<Response> {
+    match response.status {
+        status if status.is_success() => Ok(response),
+        // OAuth endpoints also answer with 401 when the client credentials are wrong
+        StatusCode::UNAUTHORIZED => Err(request
+            .errors
+            .error_of(&response)
+            .unwrap_or(RobloxError::Unauthorized(request.credential))),
+        StatusCode::TOO_MANY_REQUESTS => Err(RobloxError::RateLimited {
+            retry_after: parse_retry_after(&response.headers),
+        }),
+        _ => Err(request
+            .errors
+            .error_of(&response)
+            .unwrap_or_else(|| get_error_from_response(&response))),
+    }
+}
+
+// Sends a request through `next`, which ends with the transport of the client, and reports it
+// to its metrics hook. Only the responses to requests sent with the .ROBLOSECURITY cookie can
+// rotate it
+async fn send_request(
+    client: &RobloxClient,
+    next: Next<'_>,
+    request: Request,
+    credential: Option<Credential>,
+    retry: bool,
+    csrf_replay: bool,
+) -> RobloxResult<Response> {
+    let service = client.service_of(&request.url).map(|(service, _)| service);
+    let method = request.method.clone();
+
+    let started_at = Instant::now();
+    let result = next.run(request).await;
+    if let (Ok(response), Some(Credential::Roblosecurity)) = (&result, credential) {
+        client.update_roblosecurity(&response.headers);
+    }
+
+    if let Some(metrics_hook) = client.metrics_hook() {
+        metrics_hook.on_request(&RequestMetrics {
+            service,
+            method,
+            status_class: result
+                .as_ref()
+                .ok()
+                .map(|response| StatusClass::of(response.status)),
+            latency: started_at.elapsed(),
+            retry,
+            cache_hit: false,
+            csrf_replay,
+        });
+    }
+
+    result
+}
+
+// The body of a request
+pub(crate) enum Body {
+    Json(Value),
+    Form(Vec<(&'static str, String)>),
+}
+
+impl Body {
+    fn encode(self) -> (Vec<u8>, HeaderValue) {
+        match self {
+            Body::Json(body) => (
+                body.to_string().into_bytes(),
+                HeaderValue::from_static("application/json"),
+            ),
+            Body::Form(body) => (
+                urlencode(&body).into_bytes(),
+                HeaderValue::from_static("application/x-www-form-urlencoded"),
+            ),
+        }
+    }
+}
+
+// Which credential a request is sent with
+#[derive(Clone, Copy)]
+pub(crate) enum Auth {
+    // The one the client uses for the service of the URL
+    Default,
+    With(Credential),
+    // None at all, for requests to third parties such as an OAuth issuer
+    Anonymous,
+}
 
-    match response.status() {
-        StatusCode::OK => Ok(response),
-        StatusCode::UNAUTHORIZED => panic!(
-            "A valid .ROBLOSECURITY with sufficient permissions is required for this action."
-        ),
-        StatusCode::FORBIDDEN => {
-            // Get the x-csrf-token here because get_api_errors_from_response consumes the response
-            let x_csrf_token = response.headers().get("x-csrf-token").cloned();
-
-            let errors = get_api_errors_from_response(response).await;
-            // Some endpoints return 403 for domain logic errors, so only handle the x-csrf-token if this is a Token Validation Failed (code 0)
-            if errors.iter().any(|error| error.code == 0) {
-                headers.insert("x-csrf-token", x_csrf_token.unwrap().to_owned());
-
-                Ok(HTTP_CLIENT
-                    .request(verb, url)
-                    .headers(headers.clone())
-                    .json(&body)
-                    .send()
-                    .await
-                    .unwrap())
-            } else {
-                Err(errors)
+// What is sent on every attempt of a request
+struct PendingRequest {
+    verb: Method,
+    url: String,
+    body: Option<(Vec<u8>, HeaderValue)>,
+    credential: Option<Credential>,
+    errors: ErrorFormat,
+}
+
+// Sends the request with the headers of the current session of the client
+async fn send(
+    client: &RobloxClient,
+    request: &PendingRequest,
+    solution: Option<(&Challenge, &ChallengeSolution)>,
+    retry: bool,
+    csrf_replay: bool,
+) -> RobloxResult<Response> {
+    let mut headers = client.headers(request.credential);
+    if let Some((_, content_type)) = &request.body {
+        headers.insert(CONTENT_TYPE, content_type.clone());
+    }
+    if let Some((challenge, solution)) = solution {
+        headers.extend(solution.headers(challenge));
+    }
+
+    send_request(
+        client,
+        Next::new(client, client.middlewares()),
+        Request {
+            method: request.verb.clone(),
+            url: request.url.clone(),
+            headers,
+            body: request.body.as_ref().map(|(body, _)| body.clone()),
+        },
+        request.credential,
+        retry,
+        csrf_replay,
+    )
+    .await
+}
+
+// Sends the request, and replays it with the solution of the challenge handler of the client
+// if Roblox answers it with a challenge
+async fn send_solving_challenges(
+    client: &RobloxClient,
+    request: &PendingRequest,
+    retry: bool,
+    csrf_replay: bool,
+) -> RobloxResult<Response> {
+    let response = send(client, request, None, retry, csrf_replay).await?;
+    let challenge = match Challenge::from_headers(&response.headers) {
+        Some(challenge) if !response.status.is_success() => challenge,
+        _ => return Ok(response),
+    };
+    let handler = match client.challenge_handler() {
+        Some(handler) if !challenge::is_solving() => handler,
+        _ => return Err(RobloxError::Challenge(challenge)),
+    };
+
+    telemetry::challenge_received(&challenge);
+    let solution = challenge::solve(handler, client, &challenge).await?;
+
+    let response = send(
+        client,
+        request,
+        Some((&challenge, &solution)),
+        retry,
+        csrf_replay,
+    )
+    .await?;
+    match Challenge::from_headers(&response.headers) {
+        Some(challenge) if !response.status.is_success() => Err(RobloxError::Challenge(challenge)),
+        _ => Ok(response),
+    }
+}
+
+// Fetches an x-csrf-token before the first request that needs one, instead of waiting for it
+// to be rejected. Concurrent requests share a single fetch
+async fn ensure_csrf_token(client: &RobloxClient) -> RobloxResult<()> {
+    let csrf = client.csrf();
+    if csrf.token().is_some() {
+        return Ok(());
+    }
+
+    let _fetch = csrf.lock_fetch().await;
+    if csrf.token().is_some() {
+        return Ok(());
+    }
+
+    // Without an x-csrf-token, this request is rejected with a new one instead of logging out.
+    // The header is removed in case a concurrent request stored a token in the meantime. This
+    // request is an implementation detail, so it skips the middlewares of the client
+    let mut headers = client.headers(Some(Credential::Roblosecurity));
+    headers.remove("x-csrf-token");
+    let response = send_request(
+        client,
+        Next::new(client, &[]),
+        Request {
+            method: Method::POST,
+            url: client.endpoint(RobloxService::Auth, "/v2/logout"),
+            headers,
+            body: None,
+        },
+        Some(Credential::Roblosecurity),
+        false,
+        false,
+    )
+    .await?;
+
+    // Without a token in the response, the request goes ahead and the token is handled like
+    // an expired one
+    if let Some(token) = response.headers.get("x-csrf-token") {
+        csrf.set_token(token.clone());
+        telemetry::csrf_token_refreshed();
+    }
+    Ok(())
+}
+
+// The credential a request to `url` is sent with, failing if it needs one the client doesn't
+// have
+fn credential_for(
+    client: &RobloxClient,
+    url: &str,
+    auth: Auth,
+) -> RobloxResult<Option<Credential>> {
+    let (credential, is_required) = match auth {
+        Auth::Default => {
+            match client.default_credential(client.service_of(url).map(|(service, _)| service)) {
+                Some(default_credential) => default_credential,
+                None => return Ok(None),
+            }
+        }
+        Auth::With(credential) => (credential, true),
+        Auth::Anonymous => return Ok(None),
+    };
+    if is_required && !client.has_credential(credential) {
+        return Err(RobloxError::MissingCredential(credential));
+    }
+    Ok(Some(credential))
+}
+
+async fn request(
+    client: &RobloxClient,
+    verb: Method,
+    url: String,
+    body: Option<Body>,
+    credential: Option<Credential>,
+    errors: ErrorFormat,
+) -> RobloxResult<Response> {
+    let request = PendingRequest {
+        body: body.map(Body::encode),
+        verb,
+        url,
+        credential,
+        errors,
+    };
+    let retry_policy = client.retry_policy();
+    let span = RequestSpan::new(client, &request.verb, &request.url);
+
+    let (result, retries) = span
+        .instrument(async {
+            // Only requests sent with a .ROBLOSECURITY cookie need an x-csrf-token
+            if request.credential == Some(Credential::Roblosecurity)
+                && client.has_credential(Credential::Roblosecurity)
+                && CsrfManager::is_required_for(&request.verb)
+            {
+                if let Err(error) = ensure_csrf_token(client).await {
+                    return (Err(error), 0);
+                }
             }
+
+            let mut attempt = 1;
+            loop {
+                match request_once(client, &request, attempt > 1).await {
+                    Err(error) if retry_policy.should_retry(&request.verb, &error, attempt) => {
+                        client
+                            .runtime()
+                            .sleep(retry_policy.delay(&error, attempt))
+                            .await;
+                        attempt += 1;
+                    }
+                    result => return (result, attempt - 1),
+                }
+            }
+        })
+        .await;
+
+    span.finish(&result, retries);
+    result
+}
+
+async fn request_once(
+    client: &RobloxClient,
+    request: &PendingRequest,
+    retry: bool,
+) -> RobloxResult<Response> {
+    let response = send_solving_challenges(client, request, retry, false).await?;
+    if response.status != StatusCode::FORBIDDEN {
+        return check_status(response, request);
+    }
+
+    let error = request
+        .errors
+        .error_of(&response)
+        .unwrap_or_else(|| get_error_from_response(&response));
+    // Some endpoints return 403 for domain logic errors, so only handle the x-csrf-token if this is a Token Validation Failed (code 0)
+    if !is_token_validation_failure(&error) {
+        return Err(error);
+    }
+    client.csrf().set_token(
+        response
+            .headers
+            .get("x-csrf-token")
+            .cloned()
+            .ok_or(RobloxError::CsrfFailure)?,
+    );
+    telemetry::csrf_token_refreshed();
+
+    let response = send_solving_challenges(client, request, retry, true).await?;
+    match check_status(response, request) {
+        Err(error) if is_token_validation_failure(&error) => Err(RobloxError::CsrfFailure),
+        result => result,
+    }
+}
+
+// Sends a GET request through the cache of the client, or any other request followed by the
+// invalidation of the cached responses it made outdated
+pub(crate) async fn execute(
+    client: &RobloxClient,
+    verb: Method,
+    url: String,
+    body: Option<Body>,
+    auth: Auth,
+    errors: ErrorFormat,
+) -> RobloxResult<Response> {
+    let cache = client.cache();
+    let credential = credential_for(client, &url, auth)?;
+
+    if verb != Method::GET {
+        let response = request(client, verb, url.clone(), body, credential, errors).await?;
+        if let (Some(cache), Some((service, path))) = (cache, client.service_of(&url)) {
+            cache.invalidate_related(service, path);
+        }
+        return Ok(response);
+    }
+
+    let session = client.session_of(credential);
+    let started_at = Instant::now();
+    if let Some(response) = cache.and_then(|cache| cache.get(&url, session)) {
+        if let Some(metrics_hook) = client.metrics_hook() {
+            metrics_hook.on_request(&RequestMetrics {
+                service: client.service_of(&url).map(|(service, _)| service),
+                method: Method::GET,
+                status_class: Some(StatusClass::of(response.status)),
+                latency: started_at.elapsed(),
+                retry: false,
+                cache_hit: true,
+                csrf_replay: false,
+            });
         }
-        _ => Err(get_api_errors_from_response(response).await),
+        return Ok(response);
     }
+
+    let response = request(client, Method::GET, url.clone(), body, credential, errors).await?;
+    if let (Some(cache), Some((service, path))) = (cache, client.service_of(&url)) {
+        cache.insert(&url, session, service, path, &response);
+    }
+    Ok(response)
 }
 
-pub async fn get(url: String) -> RobloxResult<Response> {
-    request(Method::GET, url, None).await
+pub async fn get(client: &RobloxClient, url: String) -> RobloxResult<Response> {
+    execute(
+        client,
+        Method::GET,
+        url,
+        None,
+        Auth::Default,
+        ErrorFormat::Api,
+    )
+    .await
 }
 
-pub async fn delete(url: String) -> RobloxResult<Response> {
-    request(Method::DELETE, url, None).await
+pub async fn delete(client: &RobloxClient, url: String) -> RobloxResult<Response> {
+    execute(
+        client,
+        Method::DELETE,
+        url,
+        None,
+        Auth::Default,
+        ErrorFormat::Api,
+    )
+    .await
+}
+
+pub async fn post(client: &RobloxClient, url: String, body: Value) -> RobloxResult<Response> {
+    execute(
+        client,
+        Method::POST,
+        url,
+        Some(Body::Json(body)),
+        Auth::Default,
+        ErrorFormat::Api,
+    )
+    .await
+}
+
+pub async fn patch(client: &RobloxClient, url: String, body: Value) -> RobloxResult<Response> {
+    execute(
+        client,
+        Method::PATCH,
+        url,
+        Some(Body::Json(body)),
+        Auth::Default,
+        ErrorFormat::Api,
+    )
+    .await
+}
+
+// Like get, post, patch and delete, for endpoints that only work with the .ROBLOSECURITY
+// cookie. Without one, they fail with RobloxError::MissingCredential before sending anything
+pub(crate) const ROBLOSECURITY: Auth = Auth::With(Credential::Roblosecurity);
+
+pub(crate) async fn authenticated_get(
+    client: &RobloxClient,
+    url: String,
+) -> RobloxResult<Response> {
+    execute(
+        client,
+        Method::GET,
+        url,
+        None,
+        ROBLOSECURITY,
+        ErrorFormat::Api,
+    )
+    .await
 }
 
-pub async fn post(url: String, body: Value) -> RobloxResult<Response> {
-    request(Method::POST, url, Some(body)).await
+pub(crate) async fn authenticated_delete(
+    client: &RobloxClient,
+    url: String,
+) -> RobloxResult<Response> {
+    execute(
+        client,
+        Method::DELETE,
+        url,
+        None,
+        ROBLOSECURITY,
+        ErrorFormat::Api,
+    )
+    .await
 }
 
-pub async fn patch(url: String, body: Value) -> RobloxResult<Response> {
-    request(Method::PATCH, url, Some(body)).await
+pub(crate) async fn authenticated_post(
+    client: &RobloxClient,
+    url: String,
+    body: Value,
+) -> RobloxResult<Response> {
+    execute(
+        client,
+        Method::POST,
+        url,
+        Some(Body::Json(body)),
+        ROBLOSECURITY,
+        ErrorFormat::Api,
+    )
+    .await
+}
+
+pub(crate) async fn authenticated_patch(
+    client: &RobloxClient,
+    url: String,
+    body: Value,
+) -> RobloxResult<Response> {
+    execute(
+        client,
+        Method::PATCH,
+        url,
+        Some(Body::Json(body)),
+        ROBLOSECURITY,
+        ErrorFormat::Api,
+    )
+    .await
+}
+
+/// Sends a request authenticated with `credential`, failing with
+/// [RobloxError::MissingCredential] if the client doesn't have it. This is useful for
+/// endpoints this crate doesn't wrap, such as Open Cloud ones:
+///
+/// ```no_run
+/// use oxid_roblox::{
+///     util::{api_helper, Credential, RobloxService},
+///     RobloxClient,
+/// };
+/// use reqwest::Method;
+///
+/// # async fn run() -> oxid_roblox::util::RobloxResult<()> {
+/// let client = RobloxClient::builder().api_key("api key").build();
+/// let response = api_helper::request_with(
+///     &client,
+///     Method::GET,
+///     format!(
+///         "{}/cloud/v2/universes/1",
+///         client.base_url(RobloxService::Apis)
+///     ),
+///     None,
+///     Credential::ApiKey,
+/// )
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn request_with(
+    client: &RobloxClient,
+    verb: Method,
+    url: String,
+    body: Option<Value>,
+    credential: Credential,
+) -> RobloxResult<Response> {
+    execute(
+        client,
+        verb,
+        url,
+        body.map(Body::Json),
+        Auth::With(credential),
+        ErrorFormat::Api,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+
+    use super::{
+        super::transport::{Transport, TransportError},
+        *,
+    };
+
+    // Answers every request with `respond`, and keeps the requests it was sent
+    struct RecordingTransport {
+        requests: Mutex<Vec<Request>>,
+        respond: fn(&Request) -> Response,
+    }
+
+    #[async_trait]
+    impl Transport for RecordingTransport {
+        async fn send(&self, request: Request) -> Result<Response, TransportError> {
+            let response = (self.respond)(&request);
+            self.requests.lock().unwrap().push(request);
+            Ok(response)
+        }
+    }
+
+    fn empty_response(_: &Request) -> Response {
+        Response {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: b"{}".to_vec(),
+        }
+    }
+
+    fn recording_client(
+        roblosecurity: Option<&str>,
+        respond: fn(&Request) -> Response,
+    ) -> (RobloxClient, Arc<RecordingTransport>) {
+        let transport = Arc::new(RecordingTransport {
+            requests: Mutex::new(Vec::new()),
+            respond,
+        });
+        let mut builder = RobloxClient::builder().transport(transport.clone());
+        if let Some(roblosecurity) = roblosecurity {
+            builder = builder.roblosecurity(roblosecurity);
+        }
+        (builder.build(), transport)
+    }
+
+    #[tokio::test]
+    async fn sends_no_credential_outside_of_the_services_of_the_client() {
+        let (client, transport) = recording_client(Some("cookie"), empty_response);
+        get(&client, "https://evil.example/steal".to_owned())
+            .await
+            .unwrap();
+        get(&client, "https://users.roblox.com/v1/users/1".to_owned())
+            .await
+            .unwrap();
+
+        let requests = transport.requests.lock().unwrap();
+        assert!(!requests[0].headers.contains_key("Cookie"));
+        assert!(requests[1].headers.contains_key("Cookie"));
+    }
+
+    #[test]
+    fn deserialization_errors_leave_out_secrets() {
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Tokens {
+            access_token: String,
+            expires_in: u64,
+        }
+
+        let error = deserialize_body::<Tokens>(Response {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: br#"{"access_token":"access-value","refresh_token":"refresh-value","expires_in":"soon"}"#
+                .to_vec(),
+        })
+        .unwrap_err();
+
+        for output in [error.to_string(), format!("{:?}", error)] {
+            assert!(!output.contains("access-value"), "{}", output);
+            assert!(!output.contains("refresh-value"), "{}", output);
+        }
+        assert!(matches!(
+            error,
+            RobloxError::Deserialization { body_excerpt, .. } if body_excerpt.contains("soon")
+        ));
+    }
+
+    fn rotating_response(_: &Request) -> Response {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            reqwest::header::SET_COOKIE,
+            HeaderValue::from_static(".ROBLOSECURITY=rotated; domain=.roblox.com; path=/"),
+        );
+        Response {
+            status: StatusCode::OK,
+            headers,
+            body: b"{}".to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn only_requests_sent_with_the_cookie_rotate_it() {
+        let (client, transport) = recording_client(Some("cookie"), rotating_response);
+        execute(
+            &client,
+            Method::POST,
+            client.endpoint(RobloxService::Apis, "/oauth/v1/token"),
+            None,
+            Auth::Anonymous,
+            ErrorFormat::OAuth,
+        )
+        .await
+        .unwrap();
+        get(&client, "https://evil.example/rotate".to_owned())
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            get(
+                &client,
+                client.endpoint(RobloxService::Users, "/v1/users/1"),
+            )
+            .await
+            .unwrap();
+        }
+
+        let cookies = transport
+            .requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|request| {
+                request
+                    .headers
+                    .get("Cookie")
+                    .map(|cookie| cookie.to_str().unwrap().to_owned())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cookies,
+            [
+                None,
+                None,
+                Some(".ROBLOSECURITY=cookie;".to_owned()),
+                Some(".ROBLOSECURITY=rotated;".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn anonymous_clients_fetch_no_csrf_token() {
+        let (client, transport) = recording_client(None, empty_response);
+        post(
+            &client,
+            client.endpoint(RobloxService::Users, "/v1/users"),
+            serde_json::json!({ "userIds": [1] }),
+        )
+        .await
+        .unwrap();
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].url, "https://users.roblox.com/v1/users");
+    }
+
+    #[tokio::test]
+    async fn unauthorized_errors_name_the_credential_of_the_request() {
+        fn unauthorized(_: &Request) -> Response {
+            Response {
+                status: StatusCode::UNAUTHORIZED,
+                headers: HeaderMap::new(),
+                body: Vec::new(),
+            }
+        }
+
+        let (client, _) = recording_client(Some("cookie"), unauthorized);
+        client.set_api_key("key");
+        let error = get(
+            &client,
+            client.endpoint(RobloxService::Apis, "/cloud/v2/users/1"),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            RobloxError::Unauthorized(Some(Credential::ApiKey))
+        ));
+        assert!(error.to_string().contains("API key"), "{}", error);
+
+        let error = get(
+            &client,
+            client.endpoint(RobloxService::Users, "/v1/users/1"),
+        )
+        .await
+        .unwrap_err();
+        assert!(error.to_string().contains(".ROBLOSECURITY"), "{}", error);
+    }
 }
@@ -0,0 +1,351 @@
+//! Caching of GET responses
+//!
+//! A client built with [RobloxClientBuilder::cache](crate::RobloxClientBuilder::cache)
+//! keeps the successful responses of its GET requests for a configurable
+//! amount of time per [RobloxService], and serves repeated requests for the
+//! same URL with the same credential from memory:
+//!
+//! ```
+//! use std::time::Duration;
+//! use oxid_roblox::{
+//!     util::{cache::CacheConfig, RobloxService},
+//!     RobloxClient,
+//! };
+//!
+//! let client = RobloxClient::builder()
+//!     .cache(
+//!         CacheConfig::new(10_000)
+//!             .ttl(RobloxService::Users, Duration::from_secs(600))
+//!             .ttl(RobloxService::Groups, Duration::from_secs(60))
+//!             .ttl(RobloxService::Games, Duration::from_secs(60)),
+//!     )
+//!     .build();
+//! ```
+//!
+//! Successful POST, PATCH and DELETE requests invalidate the cached responses
+//! of the resource they modify. A resource is identified by the first two
+//! segments of a path after its version, so updating
+//! `/v1/groups/1/settings` invalidates `/v1/groups/1`, `/v1/groups/1/roles`
+//! and `/v2/groups/1/wall/posts` but not `/v1/groups/2`.
+//!
+//! Responses are cached apart for each value of the credential they were
+//! sent with, so changing the .ROBLOSECURITY cookie, API key or access token
+//! of a client never serves it the responses of another session.
+
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+
+use super::{transport::Response, RobloxService};
+
+/// The settings of a [ResponseCache].
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    max_entries: NonZeroUsize,
+    default_ttl: Option<Duration>,
+    ttls: HashMap<RobloxService, Duration>,
+}
+
+impl CacheConfig {
+    /// A cache of at most `max_entries` responses. Once full, the least recently used
+    /// response is evicted. Nothing is cached until a TTL is set.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries: NonZeroUsize::new(max_entries).unwrap_or(NonZeroUsize::MIN),
+            default_ttl: None,
+            ttls: HashMap::new(),
+        }
+    }
+
+    /// How long the responses of `service` are cached for.
+    pub fn ttl(mut self, service: RobloxService, ttl: Duration) -> Self {
+        self.ttls.insert(service, ttl);
+        self
+    }
+
+    /// How long the responses of services without their own TTL are cached for.
+    pub fn default_ttl(mut self, default_ttl: Duration) -> Self {
+        self.default_ttl = Some(default_ttl);
+        self
+    }
+
+    fn ttl_of(&self, service: RobloxService) -> Option<Duration> {
+        self.ttls.get(&service).copied().or(self.default_ttl)
+    }
+}
+
+// A URL, and the session of the credential it was fetched with, if any
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Key {
+    url: String,
+    session: Option<u64>,
+}
+
+struct Entry {
+    response: Response,
+    service: RobloxService,
+    resource: String,
+    expires_at: Instant,
+}
+
+/// The GET responses cached by a client, keyed by URL and credential. See
+/// [RobloxClient::cache](crate::RobloxClient::cache).
+pub struct ResponseCache {
+    config: CacheConfig,
+    entries: Mutex<LruCache<Key, Entry>>,
+}
+
+// The resource a path belongs to, e.g. "/groups/1" for "/v1/groups/1/settings?limit=10"
+fn resource_of(path: &str) -> String {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let mut segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .peekable();
+
+    let is_version = |segment: &&str| {
+        segment
+            .strip_prefix('v')
+            .is_some_and(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+    };
+    if segments.peek().is_some_and(is_version) {
+        segments.next();
+    }
+
+    segments
+        .take(2)
+        .map(|segment| format!("/{}", segment))
+        .collect()
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(config.max_entries)),
+            config,
+        }
+    }
+
+    pub(crate) fn get(&self, url: &str, session: Option<u64>) -> Option<Response> {
+        let key = Key {
+            url: url.to_owned(),
+            session,
+        };
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(&key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.response.clone()),
+            Some(_) => {
+                entries.pop(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(
+        &self,
+        url: &str,
+        session: Option<u64>,
+        service: RobloxService,
+        path: &str,
+        response: &Response,
+    ) {
+        if let Some(ttl) = self.config.ttl_of(service) {
+            self.entries.lock().unwrap().put(
+                Key {
+                    url: url.to_owned(),
+                    session,
+                },
+                Entry {
+                    response: response.clone(),
+                    service,
+                    resource: resource_of(path),
+                    expires_at: Instant::now() + ttl,
+                },
+            );
+        }
+    }
+
+    fn invalidate_where(&self, predicate: impl Fn(&Key, &Entry) -> bool) {
+        let mut entries = self.entries.lock().unwrap();
+        let keys = entries
+            .iter()
+            .filter(|(key, entry)| predicate(key, entry))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in keys {
+            entries.pop(&key);
+        }
+    }
+
+    // Invalidates every cached response of the resource `path` belongs to, for all sessions
+    pub(crate) fn invalidate_related(&self, service: RobloxService, path: &str) {
+        let resource = resource_of(path);
+        self.invalidate_where(|_, entry| entry.service == service && entry.resource == resource);
+    }
+
+    /// Removes the cached responses of `url`, whichever credential they were sent with.
+    pub fn invalidate(&self, url: &str) {
+        self.invalidate_where(|key, _| key.url == url);
+    }
+
+    /// Removes every cached response of `service`.
+    pub fn invalidate_service(&self, service: RobloxService) {
+        self.invalidate_where(|_, entry| entry.service == service);
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// The amount of cached responses, including expired ones that have not been evicted yet.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use async_trait::async_trait;
+    use reqwest::{header::HeaderMap, StatusCode};
+
+    use super::{
+        super::transport::{Request, Transport, TransportError},
+        *,
+    };
+    use crate::RobloxClient;
+
+    fn response(body: &str) -> Response {
+        Response {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    fn cache() -> ResponseCache {
+        ResponseCache::new(CacheConfig::new(10).default_ttl(Duration::from_secs(60)))
+    }
+
+    // Answers with the id of the user whose cookie the request was sent with
+    struct AuthenticatedUserTransport {
+        requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Transport for AuthenticatedUserTransport {
+        async fn send(&self, request: Request) -> Result<Response, TransportError> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let id = match request.headers.get("cookie").map(|cookie| cookie.to_str()) {
+                Some(Ok(".ROBLOSECURITY=bob;")) => 2,
+                _ => 1,
+            };
+            Ok(response(&format!(
+                r#"{{"id":{},"name":"user","displayName":"user"}}"#,
+                id
+            )))
+        }
+    }
+
+    #[test]
+    fn resource_of_skips_the_version_and_query() {
+        assert_eq!(resource_of("/v1/groups/1/settings?limit=10"), "/groups/1");
+        assert_eq!(resource_of("/v2/groups/1/wall/posts"), "/groups/1");
+        assert_eq!(
+            resource_of("/v1/users/authenticated"),
+            "/users/authenticated"
+        );
+        assert_eq!(resource_of("/v1/users"), "/users");
+        assert_eq!(resource_of("/virtual-events/1#top"), "/virtual-events/1");
+        assert_eq!(resource_of("/"), "");
+    }
+
+    #[test]
+    fn invalidates_the_responses_of_the_modified_resource() {
+        let cache = cache();
+        for path in [
+            "/v1/groups/1",
+            "/v1/groups/1/roles",
+            "/v2/groups/1/wall/posts",
+            "/v1/groups/2",
+        ] {
+            cache.insert(path, None, RobloxService::Groups, path, &response("{}"));
+        }
+        cache.insert(
+            "/v1/groups/1",
+            None,
+            RobloxService::Users,
+            "/v1/groups/1",
+            &response("{}"),
+        );
+
+        cache.invalidate_related(RobloxService::Groups, "/v1/groups/1/settings");
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("/v1/groups/2", None).is_some());
+
+        cache.invalidate_service(RobloxService::Users);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn keeps_sessions_apart() {
+        let cache = cache();
+        let path = "/v1/users/authenticated";
+        cache.insert(path, Some(1), RobloxService::Users, path, &response("1"));
+        cache.insert(path, Some(2), RobloxService::Users, path, &response("2"));
+
+        assert_eq!(cache.get(path, Some(1)).unwrap().body, b"1");
+        assert_eq!(cache.get(path, Some(2)).unwrap().body, b"2");
+        assert!(cache.get(path, None).is_none());
+
+        cache.invalidate(path);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn caches_nothing_without_a_ttl() {
+        let cache = ResponseCache::new(CacheConfig::new(10));
+        cache.insert(
+            "/v1/users/1",
+            None,
+            RobloxService::Users,
+            "/v1/users/1",
+            &response("{}"),
+        );
+        assert!(cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn never_serves_the_responses_of_another_cookie() {
+        let transport = Arc::new(AuthenticatedUserTransport {
+            requests: AtomicUsize::new(0),
+        });
+        let client = RobloxClient::builder()
+            .transport(transport.clone())
+            .roblosecurity("alice")
+            .cache(CacheConfig::new(10).default_ttl(Duration::from_secs(60)))
+            .build();
+
+        assert_eq!(client.authenticated_user().await.unwrap().id, 1);
+        assert_eq!(client.authenticated_user().await.unwrap().id, 1);
+        assert_eq!(transport.requests.load(Ordering::SeqCst), 1);
+
+        client.set_roblosecurity("bob");
+        assert_eq!(client.authenticated_user().await.unwrap().id, 2);
+        assert_eq!(transport.requests.load(Ordering::SeqCst), 2);
+    }
+}
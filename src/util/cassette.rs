@@ -0,0 +1,410 @@
+//! Recording and replaying of HTTP interactions for offline tests
+//!
+//! A [RecordingTransport] wraps another [Transport] and writes every request
+//! it sends, along with the response it got, to a JSON cassette file. The
+//! .ROBLOSECURITY cookie, API keys, bearer tokens, x-csrf-tokens, challenge
+//! solutions, and the OAuth secrets and 2-step verification codes in bodies
+//! are redacted from the file. A [ReplayTransport] then serves the responses
+//! of a cassette without touching the network, and fails every request that
+//! was not recorded.
+//!
+//! Both are regular transports, so any code using a [RobloxClient](crate::RobloxClient)
+//! works with them unchanged:
+//!
+//! ```no_run
+//! use oxid_roblox::{
+//!     util::{
+//!         cassette::{RecordingTransport, ReplayTransport},
+//!         transport::ReqwestTransport,
+//!         RetryPolicy,
+//!     },
+//!     RobloxClient,
+//! };
+//!
+//! # async fn run() -> std::io::Result<()> {
+//! // Once, with network access
+//! let client = RobloxClient::builder()
+//!     .transport(RecordingTransport::new(
+//!         ReqwestTransport::new(),
+//!         "tests/cassettes/users.json",
+//!     ))
+//!     .build();
+//! client.users_from_ids(vec![1, 156], false).await.unwrap();
+//!
+//! // In CI
+//! let client = RobloxClient::builder()
+//!     .transport(ReplayTransport::from_file("tests/cassettes/users.json")?)
+//!     .retry_policy(RetryPolicy::none())
+//!     .build();
+//! client.users_from_ids(vec![1, 156], false).await.unwrap();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Requests are matched on their method, URL and redacted body. Replaying a cassette
+//! with a retrying [RetryPolicy](super::RetryPolicy) makes unmatched requests
+//! fail only after every retry, so [RetryPolicy::none](super::RetryPolicy::none)
+//! is usually preferable.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    StatusCode,
+};
+use serde::{Deserialize, Serialize};
+
+use super::transport::{
+    redact_body, redact_request_body, Request, Response, Transport, TransportError, REDACTED,
+    SENSITIVE_HEADERS,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+/// A request and the response it got.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// The contents of a cassette file.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        serde_json::from_slice(&fs::read(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)
+    }
+}
+
+// Replaces the value of the .ROBLOSECURITY cookie in a Cookie or Set-Cookie header
+fn redact_roblosecurity(value: &str) -> String {
+    value
+        .split(';')
+        .map(
+            |pair| match pair.trim_start().strip_prefix(".ROBLOSECURITY=") {
+                Some(_) => format!(
+                    "{}.ROBLOSECURITY={}",
+                    &pair[..pair.len() - pair.trim_start().len()],
+                    REDACTED
+                ),
+                None => pair.to_owned(),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn record_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = String::from_utf8_lossy(value.as_bytes());
+            // Only the .ROBLOSECURITY pair of cookies is a secret, the others are kept so the
+            // cassette shows what was sent
+            let value = match name.as_str() {
+                "cookie" | "set-cookie" => redact_roblosecurity(&value),
+                name if SENSITIVE_HEADERS.contains(&name) => REDACTED.to_owned(),
+                _ => value.into_owned(),
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+/// A [Transport] that records every interaction of the transport it wraps to a
+/// cassette file. The file is rewritten after each interaction.
+pub struct RecordingTransport<T: Transport> {
+    inner: T,
+    path: PathBuf,
+    cassette: Mutex<Cassette>,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+    pub fn new(inner: T, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            path: path.into(),
+            cassette: Mutex::new(Cassette::default()),
+        }
+    }
+}
+
+#[async_trait]
+impl<T: Transport> Transport for RecordingTransport<T> {
+    async fn send(&self, request: Request) -> Result<Response, TransportError> {
+        let recorded_request = RecordedRequest {
+            method: request.method.to_string(),
+            url: request.url.clone(),
+            headers: record_headers(&request.headers),
+            body: request.body.as_deref().map(redact_request_body),
+        };
+
+        let response = self.inner.send(request).await?;
+
+        let mut cassette = self.cassette.lock().unwrap();
+        cassette.interactions.push(Interaction {
+            request: recorded_request,
+            response: RecordedResponse {
+                status: response.status.as_u16(),
+                headers: record_headers(&response.headers),
+                body: redact_body(&response.body),
+            },
+        });
+        cassette.save(&self.path)?;
+
+        Ok(response)
+    }
+}
+
+/// The error returned by a [ReplayTransport] for a request that is not in its cassette.
+#[derive(Debug)]
+pub struct UnmatchedRequest {
+    pub method: String,
+    pub url: String,
+}
+
+impl fmt::Display for UnmatchedRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no unused interaction of the cassette matches {} {}",
+            self.method, self.url
+        )
+    }
+}
+
+impl std::error::Error for UnmatchedRequest {}
+
+/// A [Transport] that answers requests with the responses of a cassette.
+///
+/// Each recorded interaction is served at most once, in the order it was
+/// recorded, so a cassette where the same request got different responses
+/// replays them in sequence.
+pub struct ReplayTransport {
+    // The interactions that have not been served yet
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl ReplayTransport {
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            interactions: Mutex::new(cassette.interactions),
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Cassette::load(path).map(Self::new)
+    }
+
+    /// The amount of recorded interactions that have not been replayed yet. To check it after
+    /// building a client, pass the client an `Arc<ReplayTransport>` and keep a clone of it.
+    pub fn remaining(&self) -> usize {
+        self.interactions.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn send(&self, request: Request) -> Result<Response, TransportError> {
+        let method = request.method.to_string();
+        let body = request.body.as_deref().map(redact_request_body);
+
+        let mut interactions = self.interactions.lock().unwrap();
+        let position = interactions
+            .iter()
+            .position(|interaction| {
+                interaction.request.method == method
+                    && interaction.request.url == request.url
+                    && interaction.request.body == body
+            })
+            .ok_or(UnmatchedRequest {
+                method,
+                url: request.url,
+            })?;
+        let recorded = interactions.remove(position).response;
+
+        Ok(Response {
+            status: StatusCode::from_u16(recorded.status)?,
+            headers: recorded
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((
+                        HeaderName::from_bytes(name.as_bytes()).ok()?,
+                        HeaderValue::from_str(value).ok()?,
+                    ))
+                })
+                .collect(),
+            body: recorded.body.into_bytes(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use reqwest::Method;
+
+    use super::*;
+
+    // Answers every request with a new session and the tokens of an OAuth exchange
+    struct FakeTransport;
+
+    #[async_trait]
+    impl Transport for FakeTransport {
+        async fn send(&self, _request: Request) -> Result<Response, TransportError> {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                "set-cookie",
+                HeaderValue::from_static(".ROBLOSECURITY=rotated-value; domain=.roblox.com"),
+            );
+            headers.insert("x-csrf-token", HeaderValue::from_static("csrf-value"));
+            Ok(Response {
+                status: StatusCode::OK,
+                headers,
+                body: br#"{"access_token":"access-value","token_type":"Bearer"}"#.to_vec(),
+            })
+        }
+    }
+
+    fn request(body: &str) -> Request {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "cookie",
+            HeaderValue::from_static(".ROBLOSECURITY=cookie-value; RBXSource=rbx"),
+        );
+        headers.insert("x-csrf-token", HeaderValue::from_static("csrf-value"));
+        headers.insert("x-api-key", HeaderValue::from_static("key-value"));
+        headers.insert(
+            "authorization",
+            HeaderValue::from_static("Bearer bearer-value"),
+        );
+        headers.insert(
+            "rblx-challenge-metadata",
+            HeaderValue::from_static("challenge-value"),
+        );
+        Request {
+            method: Method::POST,
+            url: "https://apis.roblox.com/oauth/v1/token".to_owned(),
+            headers,
+            body: Some(body.as_bytes().to_vec()),
+        }
+    }
+
+    fn cassette_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("oxid_roblox-{}-{}.json", name, std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn records_without_secrets() {
+        let path = cassette_path("records_without_secrets");
+        let transport = RecordingTransport::new(FakeTransport, &path);
+        transport
+            .send(request("grant_type=authorization_code&code=code-value"))
+            .await
+            .unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        for secret in [
+            "cookie-value",
+            "rotated-value",
+            "csrf-value",
+            "key-value",
+            "bearer-value",
+            "challenge-value",
+            "code-value",
+            "access-value",
+        ] {
+            assert!(!contents.contains(secret), "{} was recorded", secret);
+        }
+        assert!(contents.contains("RBXSource=rbx"));
+        assert!(contents.contains("grant_type=authorization_code"));
+    }
+
+    #[tokio::test]
+    async fn replays_recorded_interactions_once() {
+        let path = cassette_path("replays_recorded_interactions_once");
+        let body = "grant_type=authorization_code&code=code-value";
+        RecordingTransport::new(FakeTransport, &path)
+            .send(request(body))
+            .await
+            .unwrap();
+
+        let transport = ReplayTransport::from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(transport.remaining(), 1);
+
+        // A different code is redacted the same way, so it still matches
+        let response = transport
+            .send(request("grant_type=authorization_code&code=other-value"))
+            .await
+            .unwrap();
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(
+            response.headers["set-cookie"],
+            ".ROBLOSECURITY=[REDACTED]; domain=.roblox.com"
+        );
+        assert_eq!(transport.remaining(), 0);
+
+        let error = transport.send(request(body)).await.unwrap_err();
+        assert!(error.downcast_ref::<UnmatchedRequest>().is_some());
+    }
+
+    #[tokio::test]
+    async fn fails_unmatched_requests() {
+        let transport = ReplayTransport::new(Cassette {
+            interactions: vec![Interaction {
+                request: RecordedRequest {
+                    method: "POST".to_owned(),
+                    url: "https://apis.roblox.com/oauth/v1/token".to_owned(),
+                    headers: Vec::new(),
+                    body: Some("grant_type=refresh_token".to_owned()),
+                },
+                response: RecordedResponse {
+                    status: 200,
+                    headers: Vec::new(),
+                    body: "{}".to_owned(),
+                },
+            }],
+        });
+
+        let error = transport
+            .send(request("grant_type=authorization_code"))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "no unused interaction of the cassette matches POST https://apis.roblox.com/oauth/v1/token"
+        );
+        assert_eq!(transport.remaining(), 1);
+    }
+}
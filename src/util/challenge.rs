@@ -0,0 +1,275 @@
+//! Solving the challenges Roblox asks for before sensitive actions
+//!
+//! Some requests, such as changing the role of a group member on an account
+//! with 2-step verification, are answered with a challenge instead of being
+//! carried out. Without a handler, they fail with [RobloxError::Challenge].
+//! A client built with [RobloxClientBuilder::challenge_handler](crate::RobloxClientBuilder::challenge_handler)
+//! passes every challenge to its handler, and replays the request once it is
+//! solved:
+//!
+//! ```no_run
+//! use async_trait::async_trait;
+//! use oxid_roblox::{
+//!     util::{
+//!         challenge::{
+//!             solve_two_step_verification, Challenge, ChallengeHandler, ChallengeSolution,
+//!             ChallengeType,
+//!         },
+//!         RobloxError, RobloxResult,
+//!     },
+//!     RobloxClient,
+//! };
+//!
+//! # fn current_authenticator_code() -> String { String::new() }
+//! struct Authenticator;
+//!
+//! #[async_trait]
+//! impl ChallengeHandler for Authenticator {
+//!     async fn solve(
+//!         &self,
+//!         client: &RobloxClient,
+//!         challenge: &Challenge,
+//!     ) -> RobloxResult<ChallengeSolution> {
+//!         match challenge.challenge_type {
+//!             ChallengeType::TwoStepVerification => {
+//!                 solve_two_step_verification(client, challenge, &current_authenticator_code())
+//!                     .await
+//!             }
+//!             _ => Err(RobloxError::Challenge(challenge.clone())),
+//!         }
+//!     }
+//! }
+//!
+//! let client = RobloxClient::builder()
+//!     .roblosecurity("cookie")
+//!     .challenge_handler(Authenticator)
+//!     .build();
+//! ```
+
+use std::fmt;
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Method,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::RobloxClient;
+
+use super::{api_helper, Credential, RobloxError, RobloxResult, RobloxService};
+
+tokio::task_local! {
+    // Set while a challenge handler runs, so that the challenges of its own requests are not
+    // handled
+    static SOLVING: ();
+}
+
+/// The kind of a [Challenge].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ChallengeType {
+    /// A code from the authenticator app, email or phone of the account. See
+    /// [solve_two_step_verification].
+    TwoStepVerification,
+    Captcha,
+    /// The password of the account.
+    Reauthentication,
+    /// Any other kind, with the name Roblox gave it.
+    Other(String),
+}
+
+impl ChallengeType {
+    fn parse(challenge_type: &str) -> Self {
+        match challenge_type {
+            "twostepverification" => ChallengeType::TwoStepVerification,
+            "captcha" => ChallengeType::Captcha,
+            "reauthentication" => ChallengeType::Reauthentication,
+            other => ChallengeType::Other(other.to_owned()),
+        }
+    }
+
+    /// The name Roblox gives this kind of challenge, e.g. `twostepverification`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ChallengeType::TwoStepVerification => "twostepverification",
+            ChallengeType::Captcha => "captcha",
+            ChallengeType::Reauthentication => "reauthentication",
+            ChallengeType::Other(other) => other,
+        }
+    }
+}
+
+impl fmt::Display for ChallengeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A challenge Roblox asked for before carrying out a request, read from the
+/// `rblx-challenge-*` headers of its response.
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    pub id: String,
+    pub challenge_type: ChallengeType,
+    /// What solving the challenge needs, decoded from its base64 JSON. The fields depend on
+    /// the type of the challenge.
+    pub metadata: Value,
+}
+
+impl Challenge {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.to_str().ok();
+
+        Some(Self {
+            id: header("rblx-challenge-id")?.to_owned(),
+            challenge_type: ChallengeType::parse(header("rblx-challenge-type")?),
+            metadata: header("rblx-challenge-metadata")
+                .and_then(|metadata| STANDARD.decode(metadata).ok())
+                .and_then(|metadata| serde_json::from_slice(&metadata).ok())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// The proof that a [Challenge] was solved, sent along with the replayed request.
+#[derive(Clone)]
+pub struct ChallengeSolution {
+    metadata: Value,
+}
+
+impl ChallengeSolution {
+    /// A solution whose `rblx-challenge-metadata` header holds `metadata`, which is what the
+    /// challenge continue endpoint was given.
+    pub fn new(metadata: Value) -> Self {
+        Self { metadata }
+    }
+
+    // The headers the request is replayed with. A solution that is not a valid header value
+    // is left out, so the request fails with RobloxError::Challenge instead of panicking
+    pub(crate) fn headers(&self, challenge: &Challenge) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let values = [
+            ("rblx-challenge-id", challenge.id.clone()),
+            (
+                "rblx-challenge-type",
+                challenge.challenge_type.as_str().to_owned(),
+            ),
+            (
+                "rblx-challenge-metadata",
+                STANDARD.encode(self.metadata.to_string()),
+            ),
+        ];
+        for (name, value) in values {
+            if let Ok(mut value) = HeaderValue::from_str(&value) {
+                value.set_sensitive(name == "rblx-challenge-metadata");
+                headers.insert(name, value);
+            }
+        }
+        headers
+    }
+}
+
+// Its verification token is a credential
+impl fmt::Debug for ChallengeSolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChallengeSolution").finish_non_exhaustive()
+    }
+}
+
+/// Solves the challenges a client runs into. See the [module](self) documentation.
+///
+/// Returning an error fails the request that ran into the challenge with it. The requests
+/// sent by the handler go through the client as usual, but a challenge they run into is not
+/// handled.
+#[async_trait]
+pub trait ChallengeHandler: Send + Sync {
+    async fn solve(
+        &self,
+        client: &RobloxClient,
+        challenge: &Challenge,
+    ) -> RobloxResult<ChallengeSolution>;
+}
+
+pub(crate) fn is_solving() -> bool {
+    SOLVING.try_with(|_| ()).is_ok()
+}
+
+pub(crate) async fn solve(
+    handler: &dyn ChallengeHandler,
+    client: &RobloxClient,
+    challenge: &Challenge,
+) -> RobloxResult<ChallengeSolution> {
+    SOLVING.scope((), handler.solve(client, challenge)).await
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TwoStepVerificationMetadata {
+    user_id: String,
+    challenge_id: String,
+    action_type: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VerificationTokenResponse {
+    verification_token: String,
+}
+
+/// Solves a [ChallengeType::TwoStepVerification] challenge with a `code` from the
+/// authenticator app of the account.
+pub async fn solve_two_step_verification(
+    client: &RobloxClient,
+    challenge: &Challenge,
+    code: &str,
+) -> RobloxResult<ChallengeSolution> {
+    let metadata = serde_json::from_value::<TwoStepVerificationMetadata>(
+        challenge.metadata.clone(),
+    )
+    .map_err(|error| RobloxError::Deserialization {
+        message: error.to_string(),
+        body_excerpt: api_helper::body_excerpt(challenge.metadata.to_string().as_bytes()),
+    })?;
+
+    let verification = api_helper::authenticated_post(
+        client,
+        client.endpoint(
+            RobloxService::TwoStepVerification,
+            &format!(
+                "/v1/users/{}/challenges/authenticator/verify",
+                metadata.user_id
+            ),
+        ),
+        json!({
+            "challengeId": metadata.challenge_id,
+            "actionType": metadata.action_type,
+            "code": code,
+        }),
+    )
+    .await
+    .and_then(api_helper::deserialize_body::<VerificationTokenResponse>)?;
+
+    let solution = json!({
+        "verificationToken": verification.verification_token,
+        "rememberDevice": false,
+        "challengeId": metadata.challenge_id,
+        "actionType": metadata.action_type,
+    });
+    api_helper::request_with(
+        client,
+        Method::POST,
+        client.endpoint(RobloxService::Apis, "/challenge/v1/continue"),
+        Some(json!({
+            "challengeId": challenge.id,
+            "challengeType": challenge.challenge_type.as_str(),
+            "challengeMetadata": solution.to_string(),
+        })),
+        Credential::Roblosecurity,
+    )
+    .await?;
+
+    Ok(ChallengeSolution::new(solution))
+}
@@ -0,0 +1,35 @@
+use std::fmt;
+
+/// A way of authenticating the requests of a client.
+///
+/// Requests to [RobloxService::Apis](super::RobloxService::Apis) are sent with the API key of
+/// the client or, without one, its OAuth access token. They fail with
+/// [RobloxError::MissingCredential](super::RobloxError::MissingCredential) if the client has
+/// neither. Every other request is sent with the .ROBLOSECURITY cookie, if there is one, and
+/// those that only work with it, such as [RobloxClient::authenticated_user](crate::RobloxClient::authenticated_user),
+/// fail the same way without it. A credential can also be picked per request with
+/// [api_helper::request_with](super::api_helper::request_with).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Credential {
+    /// The .ROBLOSECURITY cookie, set with
+    /// [RobloxClientBuilder::roblosecurity](crate::RobloxClientBuilder::roblosecurity).
+    Roblosecurity,
+    /// An Open Cloud API key sent in the `x-api-key` header, set with
+    /// [RobloxClientBuilder::api_key](crate::RobloxClientBuilder::api_key).
+    ApiKey,
+    /// An OAuth 2.0 access token sent as a bearer token, set with
+    /// [RobloxClientBuilder::access_token](crate::RobloxClientBuilder::access_token). See
+    /// [oauth](super::oauth).
+    OAuth,
+}
+
+impl fmt::Display for Credential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Credential::Roblosecurity => write!(f, ".ROBLOSECURITY cookie"),
+            Credential::ApiKey => write!(f, "API key"),
+            Credential::OAuth => write!(f, "OAuth access token"),
+        }
+    }
+}
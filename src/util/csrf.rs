@@ -0,0 +1,43 @@
+use std::sync::RwLock;
+
+use reqwest::{header::HeaderValue, Method};
+use tokio::sync::{Mutex, MutexGuard};
+
+// The x-csrf-token of a client, shared by all of its requests. The token itself is behind a
+// regular lock that is only held to read or replace it, while fetching a new token is
+// serialised by an async lock so that concurrent requests wait for a single fetch
+pub(crate) struct CsrfManager {
+    token: RwLock<Option<HeaderValue>>,
+    fetch: Mutex<()>,
+}
+
+impl CsrfManager {
+    pub(crate) fn new() -> Self {
+        Self {
+            token: RwLock::new(None),
+            fetch: Mutex::new(()),
+        }
+    }
+
+    pub(crate) fn token(&self) -> Option<HeaderValue> {
+        self.token.read().unwrap().clone()
+    }
+
+    pub(crate) fn set_token(&self, mut token: HeaderValue) {
+        token.set_sensitive(true);
+        *self.token.write().unwrap() = Some(token);
+    }
+
+    // Held while fetching a token, see api_helper::ensure_csrf_token
+    pub(crate) async fn lock_fetch(&self) -> MutexGuard<'_, ()> {
+        self.fetch.lock().await
+    }
+
+    // Roblox asks for a token on every request that modifies data
+    pub(crate) fn is_required_for(verb: &Method) -> bool {
+        matches!(
+            *verb,
+            Method::POST | Method::PATCH | Method::PUT | Method::DELETE
+        )
+    }
+}
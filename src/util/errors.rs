@@ -1,9 +1,16 @@
-use reqwest::Response;
+use std::{fmt, sync::Arc, time::Duration};
+
+use reqwest::StatusCode;
 use serde::Deserialize;
 
-use super::{api_helper, responses::ErrorResponse};
+use super::{
+    challenge::Challenge,
+    responses::{ErrorResponse, OAuthErrorResponse},
+    transport::{Response, TransportError},
+    Credential,
+};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiError {
     pub code: i32,
@@ -11,10 +18,146 @@ pub struct ApiError {
     pub user_facing_message: Option<String>,
 }
 
-pub(crate) async fn get_api_errors_from_response(response: Response) -> Vec<ApiError> {
-    api_helper::deserialize_body::<ErrorResponse>(response)
-        .await
-        .errors
+/// The error returned by every request of this crate.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum RobloxError {
+    /// The request could not be sent, or its response could not be read.
+    Transport(Arc<dyn std::error::Error + Send + Sync>),
+    /// Roblox returned 401 Unauthorized. A valid credential with sufficient permissions is
+    /// required for this action. Holds the credential the request was sent with, if any.
+    Unauthorized(Option<Credential>),
+    /// Roblox returned 429 Too Many Requests. `retry_after` is taken from the `Retry-After`
+    /// header, if there was one.
+    RateLimited { retry_after: Option<Duration> },
+    /// A [middleware](super::middleware) refused to send the request, for the given reason.
+    Rejected(String),
+    /// The batch a [load_user](crate::RobloxClient::load_user) lookup was queued in was
+    /// dropped before it was fetched, e.g. because its task panicked. Nothing was sent for it.
+    BatchDropped,
+    /// The request needs a credential that the client was not given. Nothing was sent.
+    MissingCredential(Credential),
+    /// Roblox rejected the x-csrf-token of the request and did not provide a usable one.
+    CsrfFailure,
+    /// Roblox asked for a challenge to be solved before carrying out the request, and the
+    /// client has no [challenge handler](super::challenge::ChallengeHandler) or its solution
+    /// was not accepted.
+    Challenge(Challenge),
+    /// The body of a response did not match the expected model. `body_excerpt` holds the start
+    /// of the body.
+    Deserialization {
+        message: String,
+        body_excerpt: String,
+    },
+    /// Roblox returned an unsuccessful status code. `errors` is empty if the body of the
+    /// response was not a list of errors.
+    Api {
+        status: StatusCode,
+        errors: Vec<ApiError>,
+    },
+    /// An OAuth 2.0 endpoint rejected the request, e.g. with `invalid_grant` for an expired
+    /// authorization code or refresh token. See [oauth](super::oauth).
+    OAuth {
+        status: StatusCode,
+        error: String,
+        description: Option<String>,
+    },
+}
+
+impl fmt::Display for RobloxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RobloxError::Transport(error) => write!(f, "request failed: {}", error),
+            RobloxError::Unauthorized(Some(credential)) => write!(
+                f,
+                "a valid {} with sufficient permissions is required for this action",
+                credential
+            ),
+            RobloxError::Unauthorized(None) => write!(
+                f,
+                "this action requires a credential, and the request was sent without one"
+            ),
+            RobloxError::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(f, "rate limited, retry after {:?}", retry_after),
+            RobloxError::RateLimited { retry_after: None } => write!(f, "rate limited"),
+            RobloxError::Rejected(reason) => write!(f, "request rejected: {}", reason),
+            RobloxError::BatchDropped => {
+                write!(
+                    f,
+                    "the batch of this lookup was dropped before it was fetched"
+                )
+            }
+            RobloxError::MissingCredential(credential) => write!(
+                f,
+                "the client has no {}, which this request needs",
+                credential
+            ),
+            RobloxError::CsrfFailure => write!(f, "x-csrf-token validation failed"),
+            RobloxError::Challenge(challenge) => write!(
+                f,
+                "Roblox asked for a {} challenge to be solved",
+                challenge.challenge_type
+            ),
+            RobloxError::Deserialization {
+                message,
+                body_excerpt,
+            } => write!(
+                f,
+                "failed to deserialize response: {} (body: {})",
+                message, body_excerpt
+            ),
+            RobloxError::Api { status, errors } => {
+                write!(f, "Roblox returned {}", status)?;
+                for error in errors {
+                    write!(f, "; {} (code {})", error.message, error.code)?;
+                }
+                Ok(())
+            }
+            RobloxError::OAuth {
+                error,
+                description: Some(description),
+                ..
+            } => write!(f, "OAuth error {}: {}", error, description),
+            RobloxError::OAuth { error, .. } => write!(f, "OAuth error {}", error),
+        }
+    }
+}
+
+impl std::error::Error for RobloxError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RobloxError::Transport(error) => Some(error.as_ref() as _),
+            _ => None,
+        }
+    }
+}
+
+impl From<TransportError> for RobloxError {
+    fn from(error: TransportError) -> Self {
+        RobloxError::Transport(Arc::from(error))
+    }
+}
+
+// Only OAuth 2.0 endpoints answer with this shape. Open Cloud errors also have an `error`
+// field, so it is not tried for other responses
+pub(crate) fn get_oauth_error_from_response(response: &Response) -> Option<RobloxError> {
+    serde_json::from_slice::<OAuthErrorResponse>(&response.body)
+        .ok()
+        .map(|data| RobloxError::OAuth {
+            status: response.status,
+            error: data.error,
+            description: data.error_description,
+        })
+}
+
+pub(crate) fn get_error_from_response(response: &Response) -> RobloxError {
+    RobloxError::Api {
+        status: response.status,
+        errors: serde_json::from_slice::<ErrorResponse>(&response.body)
+            .map(|data| data.errors)
+            .unwrap_or_default(),
+    }
 }
 
-pub type RobloxResult<T> = Result<T, Vec<ApiError>>;
+pub type RobloxResult<T> = Result<T, RobloxError>;
@@ -0,0 +1,182 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::sync::oneshot;
+
+use super::{MultiGetResult, RobloxError, RobloxResult};
+use crate::RobloxClient;
+
+type Waiter<V> = oneshot::Sender<RobloxResult<Option<V>>>;
+
+// Fetches a batch of ids through one of the multi-get methods of a client
+pub(crate) type FetchBatch<V> =
+    fn(
+        RobloxClient,
+        Vec<i64>,
+    ) -> Pin<Box<dyn Future<Output = RobloxResult<MultiGetResult<i64, V>>> + Send>>;
+
+struct State<V> {
+    // The ids of the batch that has not been dispatched yet
+    queued: Vec<i64>,
+    // The waiters of every queued or in-flight id
+    waiters: HashMap<i64, Vec<Waiter<V>>>,
+}
+
+// Collects the single-id lookups made within a short window and dispatches them as one
+// multi-get. Lookups of an id that is already queued or in flight share its result
+pub(crate) struct Loader<V> {
+    window: Duration,
+    fetch_batch: FetchBatch<V>,
+    state: Arc<Mutex<State<V>>>,
+}
+
+impl<V: Clone + Send + 'static> Loader<V> {
+    pub(crate) fn new(window: Duration, fetch_batch: FetchBatch<V>) -> Self {
+        Self {
+            window,
+            fetch_batch,
+            state: Arc::new(Mutex::new(State {
+                queued: Vec::new(),
+                waiters: HashMap::new(),
+            })),
+        }
+    }
+
+    pub(crate) async fn load(&self, client: &RobloxClient, id: i64) -> RobloxResult<Option<V>> {
+        let (sender, receiver) = oneshot::channel();
+
+        {
+            let mut state = self.state.lock().unwrap();
+            match state.waiters.get_mut(&id) {
+                Some(waiters) => waiters.push(sender),
+                None => {
+                    state.waiters.insert(id, vec![sender]);
+                    state.queued.push(id);
+                    // The first lookup of a batch schedules its dispatch
+                    if state.queued.len() == 1 {
+                        client.runtime().spawn(Box::pin(Self::dispatch(
+                            client.clone(),
+                            self.window,
+                            self.fetch_batch,
+                            self.state.clone(),
+                        )));
+                    }
+                }
+            }
+        }
+
+        receiver.await.unwrap_or(Err(RobloxError::BatchDropped))
+    }
+
+    async fn dispatch(
+        client: RobloxClient,
+        window: Duration,
+        fetch_batch: FetchBatch<V>,
+        state: Arc<Mutex<State<V>>>,
+    ) {
+        let mut guard = DispatchGuard {
+            state: state.clone(),
+            ids: None,
+            is_answered: false,
+        };
+        client.runtime().sleep(window).await;
+
+        let ids = std::mem::take(&mut state.lock().unwrap().queued);
+        guard.ids = Some(ids.clone());
+        let result = fetch_batch(client, ids.clone()).await;
+
+        let mut state = state.lock().unwrap();
+        for id in ids {
+            let value = result
+                .as_ref()
+                .map(|result| result.get(&id).cloned())
+                .map_err(Clone::clone);
+            for waiter in state.waiters.remove(&id).unwrap_or_default() {
+                // The lookup may have been cancelled, in which case nobody is waiting anymore
+                let _ = waiter.send(value.clone());
+            }
+        }
+        guard.is_answered = true;
+    }
+}
+
+// Drops the waiters of a batch whose dispatch is dropped before answering them, e.g. because
+// its runtime shut down or fetching it panicked. Their lookups then fail instead of waiting
+// forever, and later lookups of the same ids start a new batch
+struct DispatchGuard<V> {
+    state: Arc<Mutex<State<V>>>,
+    // The ids of the batch, once they were taken from the queue
+    ids: Option<Vec<i64>>,
+    is_answered: bool,
+}
+
+impl<V> Drop for DispatchGuard<V> {
+    fn drop(&mut self) {
+        if self.is_answered {
+            return;
+        }
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        let ids = match self.ids.take() {
+            Some(ids) => ids,
+            None => std::mem::take(&mut state.queued),
+        };
+        for id in ids {
+            state.waiters.remove(&id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn fails_lookups_whose_batch_panicked() {
+        let loader = Loader::<i64>::new(Duration::from_millis(1), |_, _| {
+            Box::pin(async { panic!("the batch failed") })
+        });
+        let client = RobloxClient::new();
+
+        let (first, second) = tokio::join!(loader.load(&client, 1), loader.load(&client, 1));
+        assert!(matches!(first, Err(RobloxError::BatchDropped)));
+        assert!(matches!(second, Err(RobloxError::BatchDropped)));
+        assert!(loader.state.lock().unwrap().waiters.is_empty());
+    }
+
+    #[tokio::test]
+    async fn shares_the_batch_of_concurrent_lookups() {
+        let loader = Loader::new(Duration::from_millis(1), |_, ids| {
+            Box::pin(async move {
+                let mut result = MultiGetResult::new();
+                for id in ids {
+                    if id <= 2 {
+                        result.found.insert(id, id * 10);
+                    } else {
+                        result.missing.insert(id);
+                    }
+                }
+                Ok(result)
+            })
+        });
+        let client = RobloxClient::new();
+
+        let results = tokio::join!(
+            loader.load(&client, 1),
+            loader.load(&client, 2),
+            loader.load(&client, 1),
+            loader.load(&client, 3),
+        );
+        assert_eq!(results.0.unwrap(), Some(10));
+        assert_eq!(results.1.unwrap(), Some(20));
+        assert_eq!(results.2.unwrap(), Some(10));
+        assert_eq!(results.3.unwrap(), None);
+    }
+}
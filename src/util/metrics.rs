@@ -0,0 +1,139 @@
+//! Metrics of the requests sent by a client
+//!
+//! A [MetricsHook] passed to [RobloxClientBuilder::metrics_hook](crate::RobloxClientBuilder::metrics_hook)
+//! is called after every HTTP request the client sends, including retries and
+//! x-csrf-token replays, and for every response served from its
+//! [cache](super::cache). Any `Fn(&RequestMetrics)` closure is a hook:
+//!
+//! ```
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use oxid_roblox::{util::metrics::RequestMetrics, RobloxClient};
+//!
+//! static RETRIES: AtomicU64 = AtomicU64::new(0);
+//!
+//! let client = RobloxClient::builder()
+//!     .metrics_hook(|metrics: &RequestMetrics| {
+//!         if metrics.retry {
+//!             RETRIES.fetch_add(1, Ordering::Relaxed);
+//!         }
+//!     })
+//!     .build();
+//! ```
+//!
+//! With the `metrics` feature, [MetricsRecorder] reports them through the
+//! facade of the [metrics](https://docs.rs/metrics) crate.
+
+use std::time::Duration;
+
+use reqwest::{Method, StatusCode};
+
+use super::RobloxService;
+
+/// The class of an HTTP status code, e.g. [StatusClass::ClientError] for 404.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusClass {
+    Informational,
+    Success,
+    Redirection,
+    ClientError,
+    ServerError,
+}
+
+impl StatusClass {
+    pub fn of(status: StatusCode) -> Self {
+        match status.as_u16() {
+            100..=199 => StatusClass::Informational,
+            200..=299 => StatusClass::Success,
+            300..=399 => StatusClass::Redirection,
+            400..=499 => StatusClass::ClientError,
+            _ => StatusClass::ServerError,
+        }
+    }
+
+    /// The class as it is usually written, e.g. `4xx`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StatusClass::Informational => "1xx",
+            StatusClass::Success => "2xx",
+            StatusClass::Redirection => "3xx",
+            StatusClass::ClientError => "4xx",
+            StatusClass::ServerError => "5xx",
+        }
+    }
+}
+
+/// What a [MetricsHook] is told about a request.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RequestMetrics {
+    /// The family of endpoints the request was sent to, or `None` for a URL outside of the
+    /// base URLs of the client.
+    pub service: Option<RobloxService>,
+    pub method: Method,
+    /// `None` when no response was received.
+    pub status_class: Option<StatusClass>,
+    /// The time spent in the transport, or in the cache lookup for cache hits.
+    pub latency: Duration,
+    /// Whether the request was sent again by the [RetryPolicy](super::RetryPolicy).
+    pub retry: bool,
+    /// Whether the response was served from the cache, without sending the request.
+    pub cache_hit: bool,
+    /// Whether the request was sent again with a refreshed x-csrf-token.
+    pub csrf_replay: bool,
+}
+
+/// Receives the [RequestMetrics] of every request sent by a client.
+///
+/// It is called on the task that sent the request, so it should return quickly.
+pub trait MetricsHook: Send + Sync {
+    fn on_request(&self, metrics: &RequestMetrics);
+}
+
+impl<F: Fn(&RequestMetrics) + Send + Sync> MetricsHook for F {
+    fn on_request(&self, metrics: &RequestMetrics) {
+        self(metrics)
+    }
+}
+
+/// A [MetricsHook] that records requests through the [metrics](https://docs.rs/metrics) crate:
+///
+/// - `roblox_requests_total`, a counter labelled with `service`, `method`, `status_class`,
+///   `retry`, `cache_hit` and `csrf_replay`
+/// - `roblox_request_duration_seconds`, a histogram labelled with `service`, `method` and
+///   `status_class`
+///
+/// A `status_class` of `none` means no response was received.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetricsRecorder;
+
+#[cfg(feature = "metrics")]
+impl MetricsHook for MetricsRecorder {
+    fn on_request(&self, metrics: &RequestMetrics) {
+        let service = metrics
+            .service
+            .map_or("unknown", |service| service.subdomain());
+        let method = metrics.method.to_string();
+        let status_class = metrics
+            .status_class
+            .map_or("none", |status_class| status_class.as_str());
+
+        ::metrics::counter!(
+            "roblox_requests_total",
+            "service" => service,
+            "method" => method.clone(),
+            "status_class" => status_class,
+            "retry" => metrics.retry.to_string(),
+            "cache_hit" => metrics.cache_hit.to_string(),
+            "csrf_replay" => metrics.csrf_replay.to_string(),
+        )
+        .increment(1);
+        ::metrics::histogram!(
+            "roblox_request_duration_seconds",
+            "service" => service,
+            "method" => method,
+            "status_class" => status_class,
+        )
+        .record(metrics.latency.as_secs_f64());
+    }
+}
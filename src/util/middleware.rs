@@ -0,0 +1,121 @@
+//! Layers around every request a client sends
+//!
+//! A client built with [RobloxClientBuilder::middleware](crate::RobloxClientBuilder::middleware)
+//! passes each outgoing [Request] through its middlewares, in the order they
+//! were added, before handing it to its [Transport](super::transport::Transport).
+//! Each middleware gets the response of the next one on the way back, and can
+//! change the request or the response, or answer without calling the next
+//! one at all:
+//!
+//! ```
+//! use async_trait::async_trait;
+//! use oxid_roblox::{
+//!     util::{
+//!         middleware::{Middleware, Next},
+//!         transport::{Request, Response},
+//!         RobloxError, RobloxResult,
+//!     },
+//!     RobloxClient,
+//! };
+//! use reqwest::{header::HeaderValue, Method};
+//!
+//! // Tags every request with the environment it comes from
+//! struct Environment(&'static str);
+//!
+//! #[async_trait]
+//! impl Middleware for Environment {
+//!     async fn handle(&self, mut request: Request, next: Next<'_>) -> RobloxResult<Response> {
+//!         request
+//!             .headers
+//!             .insert("x-environment", HeaderValue::from_static(self.0));
+//!         next.run(request).await
+//!     }
+//! }
+//!
+//! // Only lets reads and group role changes through
+//! struct AllowList;
+//!
+//! #[async_trait]
+//! impl Middleware for AllowList {
+//!     async fn handle(&self, request: Request, next: Next<'_>) -> RobloxResult<Response> {
+//!         let is_role_change = request.method == Method::PATCH
+//!             && request.url.starts_with("https://groups.roblox.com/v1/groups/")
+//!             && request.url.contains("/users/");
+//!         if request.method != Method::GET && !is_role_change {
+//!             return Err(RobloxError::Rejected(format!("{} is not allowed", request.url)));
+//!         }
+//!         next.run(request).await
+//!     }
+//! }
+//!
+//! let client = RobloxClient::builder()
+//!     .middleware(Environment("staging"))
+//!     .middleware(AllowList)
+//!     .build();
+//! ```
+//!
+//! Middlewares run for every request that is sent, including retries,
+//! x-csrf-token replays and the requests of challenge handlers, but not for
+//! responses served from the cache. The request a client sends to fetch an
+//! x-csrf-token before its first modifying request skips them, so they only
+//! see requests that were asked for. The request a middleware sees already
+//! holds the credentials of the client.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::RobloxClient;
+
+use super::{
+    transport::{Request, Response},
+    RobloxResult,
+};
+
+/// A layer around the requests of a client. See the [module](self) documentation.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Handles `request`, usually by passing it on with [Next::run].
+    async fn handle(&self, request: Request, next: Next<'_>) -> RobloxResult<Response>;
+}
+
+/// The rest of the chain after a [Middleware]. It can be run more than once, e.g. to retry
+/// a request.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    client: &'a RobloxClient,
+    middlewares: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(client: &'a RobloxClient, middlewares: &'a [Arc<dyn Middleware>]) -> Self {
+        Self {
+            client,
+            middlewares,
+        }
+    }
+
+    /// The client sending the request.
+    pub fn client(&self) -> &'a RobloxClient {
+        self.client
+    }
+
+    /// Passes `request` to the next middleware, or to the transport of the client after the
+    /// last one.
+    pub async fn run(self, request: Request) -> RobloxResult<Response> {
+        match self.middlewares.split_first() {
+            Some((middleware, middlewares)) => {
+                middleware
+                    .handle(
+                        request,
+                        Next {
+                            middlewares,
+                            ..self
+                        },
+                    )
+                    .await
+            }
+            None => Ok(self.client.transport().send(request).await?),
+        }
+    }
+}
@@ -1,10 +1,30 @@
+mod credential;
+pub use credential::Credential;
+
 mod errors;
 pub use errors::*;
 
-mod result_extensions;
-pub(crate) use result_extensions::ResultExtensions;
+mod multi_get;
+pub use multi_get::MultiGetResult;
+
+mod retry;
+pub use retry::RetryPolicy;
+
+mod service;
+pub use service::RobloxService;
 
 pub mod api_helper;
+pub mod cache;
+pub mod cassette;
+pub mod challenge;
+pub(crate) mod csrf;
+pub(crate) mod loader;
+pub mod metrics;
+pub mod middleware;
+pub mod oauth;
 pub mod paging;
 pub(crate) mod parsers;
 pub(crate) mod responses;
+pub mod runtime;
+pub(crate) mod telemetry;
+pub mod transport;
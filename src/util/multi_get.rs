@@ -0,0 +1,35 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+/// The result of a keyed multi-get such as
+/// [RobloxClient::users_from_ids_keyed](crate::RobloxClient::users_from_ids_keyed).
+///
+/// Every requested key ends up in exactly one of `found` or `missing`.
+#[derive(Debug, Clone)]
+pub struct MultiGetResult<K, V> {
+    /// What Roblox returned for each requested key.
+    pub found: HashMap<K, V>,
+    /// The requested keys Roblox returned nothing for, either because they don't exist or
+    /// because they were filtered out (e.g. banned users with `exclude_banned_users`).
+    pub missing: HashSet<K>,
+}
+
+impl<K: Eq + Hash, V> MultiGetResult<K, V> {
+    pub(crate) fn new() -> Self {
+        Self {
+            found: HashMap::new(),
+            missing: HashSet::new(),
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.found.get(key)
+    }
+
+    /// Whether Roblox returned something for every requested key.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
@@ -0,0 +1,337 @@
+//! Signing users in with OAuth 2.0
+//!
+//! An [OAuthApp] holds the registration of a third-party app and runs the
+//! authorization code flow with PKCE against Roblox, or against any other
+//! issuer set with [OAuthApp::issuer], such as a local stand-in for tests.
+//! Its requests are sent through the transport of the client they are given,
+//! without its .ROBLOSECURITY cookie.
+//!
+//! ```no_run
+//! use oxid_roblox::{util::oauth::OAuthApp, RobloxClient};
+//!
+//! # async fn run(code: &str) -> oxid_roblox::util::RobloxResult<()> {
+//! let app = OAuthApp::new("client id", "https://example.com/callback")
+//!     .client_secret("client secret");
+//!
+//! // Redirect the user to `request.url`, keeping `request.state` and
+//! // `request.code_verifier` until they come back to the redirect URI
+//! let request = app.authorize(&["openid", "profile"]);
+//!
+//! // Once the `state` parameter of the redirect is checked against `request.state`
+//! let client = RobloxClient::new();
+//! let tokens = app
+//!     .exchange_code(&client, code, &request.code_verifier)
+//!     .await?;
+//!
+//! // A client acting on behalf of the user
+//! let client = RobloxClient::builder()
+//!     .access_token(&tokens.access_token)
+//!     .build();
+//! let user_info = app.user_info(&client).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Access tokens expire after [TokenSet::expires_in] seconds. A new one is
+//! obtained with [OAuthApp::refresh], and given to the client with
+//! [RobloxClient::set_access_token].
+
+use std::{collections::HashMap, fmt};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::RobloxClient;
+
+use super::{
+    api_helper::{self, Auth, Body, ErrorFormat},
+    Credential, RobloxResult,
+};
+
+const DEFAULT_ISSUER: &str = "https://apis.roblox.com/oauth";
+
+// A random string of 43 URL-safe characters, as used for PKCE code verifiers and states
+fn random_token() -> String {
+    let mut bytes = [0; 32];
+    // Only fails on platforms without a source of randomness, where no flow could be secure
+    getrandom::getrandom(&mut bytes).expect("failed to generate random bytes");
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+// The S256 code challenge of a PKCE code verifier
+fn code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+/// The URL to send a user to for them to authorize an [OAuthApp], along with what is needed
+/// to finish the flow once they come back.
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    pub url: String,
+    /// Must match the `state` parameter of the redirect, to make sure the user started the flow.
+    pub state: String,
+    /// The PKCE code verifier to pass to [OAuthApp::exchange_code].
+    pub code_verifier: String,
+}
+
+/// The tokens an [OAuthApp] gets for a user.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TokenSet {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub token_type: String,
+    /// How many seconds the access token is valid for.
+    pub expires_in: u64,
+    /// Only returned if the `openid` scope was granted.
+    pub id_token: Option<String>,
+    /// The granted scopes, separated by spaces.
+    pub scope: String,
+}
+
+impl fmt::Debug for TokenSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenSet")
+            .field("access_token", &"[REDACTED]")
+            .field("refresh_token", &"[REDACTED]")
+            .field("token_type", &self.token_type)
+            .field("expires_in", &self.expires_in)
+            .field("id_token", &self.id_token.as_ref().map(|_| "[REDACTED]"))
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+/// The user an access token was granted by. Only `sub` is always present, the other fields
+/// depend on the granted scopes.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserInfo {
+    /// The id of the user.
+    pub sub: String,
+    pub name: Option<String>,
+    pub nickname: Option<String>,
+    pub preferred_username: Option<String>,
+    /// When the account was created, as a Unix timestamp.
+    pub created_at: Option<i64>,
+    pub profile: Option<String>,
+    pub picture: Option<String>,
+}
+
+impl UserInfo {
+    pub fn user_id(&self) -> Option<i64> {
+        self.sub.parse().ok()
+    }
+}
+
+/// The user or group that granted access to some of its resources.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResourceOwner {
+    pub id: String,
+    /// `User` or `Group`.
+    #[serde(rename = "type")]
+    pub owner_type: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResourceIds {
+    pub ids: Vec<String>,
+}
+
+/// The resources of an owner an access token can be used on, keyed by their type, such as
+/// `universe` or `creator`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResourceInfo {
+    pub owner: ResourceOwner,
+    pub resources: HashMap<String, ResourceIds>,
+}
+
+#[derive(Deserialize)]
+struct TokenResourcesResponse {
+    resource_infos: Vec<ResourceInfo>,
+}
+
+/// A third-party app registered with an OAuth 2.0 issuer. See the [module](self) documentation.
+#[derive(Clone)]
+pub struct OAuthApp {
+    client_id: String,
+    client_secret: Option<String>,
+    redirect_uri: String,
+    issuer: String,
+}
+
+impl fmt::Debug for OAuthApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuthApp")
+            .field("client_id", &self.client_id)
+            .field(
+                "client_secret",
+                &self.client_secret.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("redirect_uri", &self.redirect_uri)
+            .field("issuer", &self.issuer)
+            .finish()
+    }
+}
+
+impl OAuthApp {
+    /// A public app, which authenticates with PKCE only. `redirect_uri` must be one of the
+    /// redirect URIs of the app.
+    pub fn new(client_id: &str, redirect_uri: &str) -> Self {
+        Self {
+            client_id: client_id.to_owned(),
+            client_secret: None,
+            redirect_uri: redirect_uri.to_owned(),
+            issuer: DEFAULT_ISSUER.to_owned(),
+        }
+    }
+
+    /// Makes this a confidential app, which also authenticates with its secret.
+    pub fn client_secret(mut self, client_secret: &str) -> Self {
+        self.client_secret = Some(client_secret.to_owned());
+        self
+    }
+
+    /// The URL the endpoints of the issuer are under, e.g. `http://localhost:8080/oauth` for
+    /// `http://localhost:8080/oauth/v1/token`. Defaults to `https://apis.roblox.com/oauth`.
+    pub fn issuer(mut self, issuer: &str) -> Self {
+        self.issuer = issuer.trim_end_matches('/').to_owned();
+        self
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}{}", self.issuer, path)
+    }
+
+    // The client authentication sent in the body of every request to the token endpoints
+    fn form(&self, mut params: Vec<(&'static str, String)>) -> Body {
+        params.push(("client_id", self.client_id.clone()));
+        if let Some(client_secret) = &self.client_secret {
+            params.push(("client_secret", client_secret.clone()));
+        }
+        Body::Form(params)
+    }
+
+    async fn post_form<T: serde::de::DeserializeOwned>(
+        &self,
+        client: &RobloxClient,
+        path: &str,
+        params: Vec<(&'static str, String)>,
+    ) -> RobloxResult<T> {
+        api_helper::execute(
+            client,
+            Method::POST,
+            self.endpoint(path),
+            Some(self.form(params)),
+            Auth::Anonymous,
+            ErrorFormat::OAuth,
+        )
+        .await
+        .and_then(api_helper::deserialize_body)
+    }
+
+    /// Starts a flow asking the user for `scopes`, with a new state and PKCE code verifier.
+    pub fn authorize(&self, scopes: &[&str]) -> AuthorizationRequest {
+        let state = random_token();
+        let code_verifier = random_token();
+
+        let query = api_helper::urlencode(&[
+            ("client_id", self.client_id.as_str()),
+            ("redirect_uri", &self.redirect_uri),
+            ("scope", &scopes.join(" ")),
+            ("response_type", "code"),
+            ("state", &state),
+            ("code_challenge", &code_challenge(&code_verifier)),
+            ("code_challenge_method", "S256"),
+        ]);
+
+        AuthorizationRequest {
+            url: format!("{}?{}", self.endpoint("/v1/authorize"), query),
+            state,
+            code_verifier,
+        }
+    }
+
+    /// Exchanges the `code` parameter of the redirect for the tokens of the user.
+    /// `code_verifier` is the one of the [AuthorizationRequest] the flow started with.
+    pub async fn exchange_code(
+        &self,
+        client: &RobloxClient,
+        code: &str,
+        code_verifier: &str,
+    ) -> RobloxResult<TokenSet> {
+        self.post_form(
+            client,
+            "/v1/token",
+            vec![
+                ("grant_type", "authorization_code".to_owned()),
+                ("code", code.to_owned()),
+                ("code_verifier", code_verifier.to_owned()),
+                ("redirect_uri", self.redirect_uri.clone()),
+            ],
+        )
+        .await
+    }
+
+    /// Gets new tokens with a refresh token. The old refresh token can't be used again.
+    pub async fn refresh(
+        &self,
+        client: &RobloxClient,
+        refresh_token: &str,
+    ) -> RobloxResult<TokenSet> {
+        self.post_form(
+            client,
+            "/v1/token",
+            vec![
+                ("grant_type", "refresh_token".to_owned()),
+                ("refresh_token", refresh_token.to_owned()),
+            ],
+        )
+        .await
+    }
+
+    /// Revokes a refresh token, along with the access tokens obtained with it.
+    pub async fn revoke(&self, client: &RobloxClient, refresh_token: &str) -> RobloxResult<()> {
+        api_helper::execute(
+            client,
+            Method::POST,
+            self.endpoint("/v1/token/revoke"),
+            Some(self.form(vec![("token", refresh_token.to_owned())])),
+            Auth::Anonymous,
+            ErrorFormat::OAuth,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// The resources the user let `access_token` be used on.
+    pub async fn token_resources(
+        &self,
+        client: &RobloxClient,
+        access_token: &str,
+    ) -> RobloxResult<Vec<ResourceInfo>> {
+        self.post_form::<TokenResourcesResponse>(
+            client,
+            "/v1/token/resources",
+            vec![("token", access_token.to_owned())],
+        )
+        .await
+        .map(|data| data.resource_infos)
+    }
+
+    /// The user who granted the access token of `client`. Fails with
+    /// [RobloxError::MissingCredential](super::RobloxError::MissingCredential) if `client` has
+    /// no access token.
+    pub async fn user_info(&self, client: &RobloxClient) -> RobloxResult<UserInfo> {
+        api_helper::execute(
+            client,
+            Method::GET,
+            self.endpoint("/v1/userinfo"),
+            None,
+            Auth::With(Credential::OAuth),
+            ErrorFormat::OAuth,
+        )
+        .await
+        .and_then(api_helper::deserialize_body)
+    }
+}
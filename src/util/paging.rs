@@ -6,14 +6,16 @@
 //! from the `futures_util` crate, you can do many sorts of manipulations
 //! on the stream. A full example:
 //!
-//! ```
+//! ```no_run
 //! use futures_util::{pin_mut, StreamExt};
 //! use oxid_roblox::derives::{GroupDerive, UserDerive};
 //!
 //! #[tokio::main]
 //! async fn main() {
+//!     let client = oxid_roblox::RobloxClient::new();
+//!
 //!     let user = oxid_roblox::base_user(1);
-//!     let username_iterator = user.username_history();
+//!     let username_iterator = user.username_history(&client);
 //!
 //!     let first_5_usernames = username_iterator
 //!         .into_stream()
@@ -25,7 +27,7 @@
 //!     println!("{:?}", first_5_usernames);
 //!
 //!     let group = oxid_roblox::base_group(1);
-//!     let members_stream = group.members().into_stream();
+//!     let members_stream = group.members(&client).into_stream();
 //!     pin_mut!(members_stream);
 //!
 //!     while let Some(Ok(member)) = members_stream.next().await {
@@ -34,27 +36,151 @@
 //! }
 //! ```
 //!
+//! [PageIterator::into_pages_stream] yields whole pages instead, and
+//! [PageIterator::take_n] and [PageIterator::collect_all] fetch pages until
+//! they have enough items:
+//!
+//! ```no_run
+//! use oxid_roblox::derives::GroupDerive;
+//!
+//! # async fn run() -> oxid_roblox::util::RobloxResult<()> {
+//! let client = oxid_roblox::RobloxClient::new();
+//! let first_250_members = oxid_roblox::base_group(1)
+//!     .members(&client)
+//!     .take_n(250)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Large iterations can fetch the next pages while the current one is
+//! consumed, with [PageIterator::prefetch]:
+//!
+//! ```no_run
+//! use oxid_roblox::derives::GroupDerive;
+//!
+//! # async fn run() {
+//! let client = oxid_roblox::RobloxClient::new();
+//! let members_stream = oxid_roblox::base_group(1)
+//!     .members(&client)
+//!     .prefetch(2)
+//!     .into_stream();
+//! # }
+//! ```
+//!
 //! To change how many items per page should be fetched, use the
 //! [PageIterator::page_size] method. To change the sort order, use the
 //! [PageIterator::sort_order] method. For example:
 //!
 //! ```
+//! use oxid_roblox::derives::UserDerive;
+//! use oxid_roblox::util::paging::{PageSize, SortOrder};
+//!
 //! let usernames_iterator = oxid_roblox::base_user(1)
-//!     .username_history()
+//!     .username_history(oxid_roblox::default_client())
 //!     .page_size(PageSize::OneHundred)
 //!     .sort_order(SortOrder::Descending);
 //! ```
+//!
+//! Items can be mapped with a closure, e.g. to tag them with the id they
+//! were fetched for, with [PageIterator::map]. [PageIterator::filter_map]
+//! also drops items before they are buffered:
+//!
+//! ```no_run
+//! use oxid_roblox::derives::GroupDerive;
+//!
+//! #[derive(Clone)]
+//! struct GroupMember {
+//!     group_id: i64,
+//!     user_id: i64,
+//! }
+//!
+//! # async fn run() -> oxid_roblox::util::RobloxResult<()> {
+//! let client = oxid_roblox::RobloxClient::new();
+//! let group_id = 1;
+//! let members = oxid_roblox::base_group(group_id)
+//!     .members(&client)
+//!     .filter_map(move |member| {
+//!         (member.role.rank >= 200).then(|| GroupMember {
+//!             group_id,
+//!             user_id: member.user.id,
+//!         })
+//!     })
+//!     .collect_all()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A long iteration can be checkpointed by fetching its pages one at a time
+//! with [PageIterator::next_page] and saving its [PageIteratorState] after
+//! each of them. [PageIterator::resume_from] then picks it up where it was left:
+//!
+//! ```no_run
+//! use oxid_roblox::{derives::GroupDerive, util::paging::PageIteratorState};
+//!
+//! # async fn run(saved_state: Option<String>) -> oxid_roblox::util::RobloxResult<()> {
+//! let client = oxid_roblox::RobloxClient::new();
+//! let mut members = oxid_roblox::base_group(1).members(&client);
+//! if let Some(saved_state) = saved_state {
+//!     members = members.resume_from(
+//!         serde_json::from_str::<PageIteratorState>(&saved_state).unwrap(),
+//!     );
+//! }
+//!
+//! while let Some(page) = members.next_page().await? {
+//!     println!("{:?}", page.items);
+//!     // Save the checkpoint once the page is exported
+//!     let checkpoint = serde_json::to_string(&members.state()).unwrap();
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Pages can also be browsed in both directions, e.g. for a "previous page"
+//! button, with [PageIterator::previous_page], or with [PageIterator::fetch_page]
+//! and the cursors of a [Page]:
+//!
+//! ```no_run
+//! use oxid_roblox::derives::GroupDerive;
+//!
+//! # async fn run() -> oxid_roblox::util::RobloxResult<()> {
+//! let client = oxid_roblox::RobloxClient::new();
+//! let mut members = oxid_roblox::base_group(1).members(&client);
+//!
+//! let first = members.fetch_page(None).await?;
+//! if let Some(next_cursor) = &first.next_cursor {
+//!     let second = members.fetch_page(Some(next_cursor)).await?;
+//!     assert!(second.has_previous());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
 
 use async_stream::stream;
-use async_trait::async_trait;
 use futures_core::stream::Stream;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::RobloxClient;
 
-use super::{api_helper, responses::PageResponse, RobloxResult};
+use super::{
+    api_helper::{self, Auth, ErrorFormat},
+    responses::PageResponse,
+    telemetry, RobloxResult,
+};
 
 pub(crate) fn identity_mapper<T: Clone>(data: &T) -> T {
     data.clone()
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortOrder {
     Ascending,
     Descending,
@@ -70,6 +196,7 @@ impl SortOrder {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PageSize {
     Ten = 10,
     TwentyFive = 25,
@@ -90,14 +217,18 @@ impl PageSize {
 }
 
 // An iterator for all pages of a PageIterator
-struct PagesIterator<T> {
-    iterator: Box<dyn BasePageIterator<T>>,
+struct PagesIterator<I, T> {
+    iterator: I,
     current_page_position: i32,
     current_page_data: Vec<T>,
 }
 
-impl<T: Clone> PagesIterator<T> {
-    fn new(iterator: Box<dyn BasePageIterator<T>>) -> Self {
+impl<I, T> PagesIterator<I, T>
+where
+    I: BasePageIterator<T> + Send + 'static,
+    T: Clone + Send + 'static,
+{
+    fn new(iterator: I) -> Self {
         Self {
             iterator,
             current_page_position: 0,
@@ -105,7 +236,7 @@ impl<T: Clone> PagesIterator<T> {
         }
     }
 
-    fn into_stream(mut self) -> impl Stream<Item = RobloxResult<T>> {
+    fn into_stream(mut self) -> impl Stream<Item = RobloxResult<T>> + Send + 'static {
         stream! {
             loop {
                 if self.current_page_position == self.current_page_data.len() as i32 {
@@ -118,10 +249,11 @@ impl<T: Clone> PagesIterator<T> {
                         None => break,
                     };
 
+                    self.current_page_position = 0;
+                    // Every item of a page can be filtered out, with more pages after it
                     if self.current_page_data.is_empty() {
-                        break;
+                        continue;
                     }
-                    self.current_page_position = 0;
                 }
 
                 yield Ok(self.current_page_data[self.current_page_position as usize].clone());
@@ -131,9 +263,51 @@ impl<T: Clone> PagesIterator<T> {
     }
 }
 
-#[async_trait(?Send)]
+/// A page of items, along with the cursors of the pages around it.
+#[derive(Debug, Clone)]
+pub struct Page<U> {
+    pub items: Vec<U>,
+    /// The cursor the page was fetched with, `None` for the first page.
+    pub cursor: Option<String>,
+    pub previous_cursor: Option<String>,
+    pub next_cursor: Option<String>,
+    /// How long fetching the page took, including retries.
+    pub latency: Duration,
+}
+
+impl<U> Page<U> {
+    pub fn has_previous(&self) -> bool {
+        self.previous_cursor.is_some()
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+}
+
+/// Where a [PageIterator] is in its iteration, to pick it up later with
+/// [PageIterator::resume_from].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PageIteratorState {
+    /// The URL of the endpoint, without the paging parameters. Only kept to tell checkpoints
+    /// apart: [PageIterator::resume_from] keeps fetching from the URL of the iterator.
+    pub url: String,
+    pub sort_order: SortOrder,
+    pub page_size: PageSize,
+    /// The cursor of the next page, `None` for the first one.
+    pub cursor: Option<String>,
+    /// The cursor of the page before the one last fetched.
+    #[serde(default)]
+    pub previous_cursor: Option<String>,
+    /// Whether the last page was fetched.
+    pub is_finished: bool,
+}
+
+// Maps the items of a page, dropping those it returns None for
+type Mapper<T, U> = Box<dyn FnMut(&T) -> Option<U> + Send>;
+
 trait BasePageIterator<T> {
-    async fn next_page(&mut self) -> RobloxResult<Option<Vec<T>>>;
+    fn next_page(&mut self) -> impl Future<Output = RobloxResult<Option<Vec<T>>>> + Send;
 }
 
 pub struct PageIterator<T, U>
@@ -141,30 +315,59 @@ where
     T: serde::de::DeserializeOwned,
     U: Clone,
 {
+    client: RobloxClient,
     url: String,
-    mapper: fn(&T) -> U,
+    mapper: Mapper<T, U>,
+    auth: Auth,
     sort_order: SortOrder,
     page_size: PageSize,
+    prefetch: usize,
     iteration_started: bool,
+    previous_cursor: Option<String>,
     next_cursor: Option<String>,
 }
 
 impl<T, U> PageIterator<T, U>
 where
-    T: serde::de::DeserializeOwned + 'static,
-    U: Clone + 'static,
+    T: serde::de::DeserializeOwned + Send + 'static,
+    U: Clone + Send + 'static,
 {
-    pub fn new(url: String, mapper: fn(&T) -> U) -> Self {
+    pub fn new(
+        client: RobloxClient,
+        url: String,
+        mut mapper: impl FnMut(&T) -> U + Send + 'static,
+    ) -> Self {
+        Self::new_filter_map(client, url, move |data| Some(mapper(data)))
+    }
+
+    /// Like [PageIterator::new], but drops the items `mapper` returns `None` for before they
+    /// are buffered.
+    pub fn new_filter_map(
+        client: RobloxClient,
+        url: String,
+        mapper: impl FnMut(&T) -> Option<U> + Send + 'static,
+    ) -> Self {
         Self {
+            client,
             url,
-            mapper,
+            mapper: Box::new(mapper),
+            auth: Auth::Default,
             sort_order: SortOrder::Ascending,
             page_size: PageSize::Ten,
+            prefetch: 0,
             iteration_started: false,
+            previous_cursor: None,
             next_cursor: None,
         }
     }
 
+    // For endpoints that only work with the .ROBLOSECURITY cookie, see
+    // api_helper::ROBLOSECURITY
+    pub(crate) fn authenticated(mut self) -> Self {
+        self.auth = api_helper::ROBLOSECURITY;
+        self
+    }
+
     pub fn sort_order(mut self, sort_order: SortOrder) -> Self {
         self.sort_order = sort_order;
         self
@@ -175,38 +378,430 @@ where
         self
     }
 
-    pub fn into_stream(self) -> impl Stream<Item = RobloxResult<U>> {
-        PagesIterator::new(Box::new(self)).into_stream()
+    /// Lets [PageIterator::into_stream] and [PageIterator::into_pages_stream] fetch up to
+    /// `prefetch` pages ahead of the one being consumed, from a task spawned on the
+    /// [runtime](super::runtime) of the client. 0 by default, which fetches each page once the
+    /// previous one is consumed.
+    pub fn prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
     }
-}
 
-#[async_trait(?Send)]
-impl<T, U> BasePageIterator<U> for PageIterator<T, U>
-where
-    T: serde::de::DeserializeOwned,
-    U: Clone,
-{
-    async fn next_page(&mut self) -> RobloxResult<Option<Vec<U>>> {
+    /// Maps every item with `mapper`, e.g. to tag it with the id it was fetched for.
+    pub fn map<V>(self, mut mapper: impl FnMut(U) -> V + Send + 'static) -> PageIterator<T, V>
+    where
+        V: Clone + Send + 'static,
+    {
+        self.filter_map(move |item| Some(mapper(item)))
+    }
+
+    /// Maps every item with `mapper`, dropping those it returns `None` for before they are
+    /// buffered. Pages can end up with fewer items than their page size, or none at all.
+    pub fn filter_map<V>(
+        self,
+        mut mapper: impl FnMut(U) -> Option<V> + Send + 'static,
+    ) -> PageIterator<T, V>
+    where
+        V: Clone + Send + 'static,
+    {
+        let mut inner = self.mapper;
+        PageIterator {
+            client: self.client,
+            url: self.url,
+            mapper: Box::new(move |data| inner(data).and_then(&mut mapper)),
+            auth: self.auth,
+            sort_order: self.sort_order,
+            page_size: self.page_size,
+            prefetch: self.prefetch,
+            iteration_started: self.iteration_started,
+            previous_cursor: self.previous_cursor,
+            next_cursor: self.next_cursor,
+        }
+    }
+
+    /// Starts the iteration at the page of `cursor`, as returned by [PageIterator::cursor] or
+    /// [Page::next_cursor].
+    pub fn starting_at(mut self, cursor: &str) -> Self {
+        self.iteration_started = true;
+        self.next_cursor = Some(cursor.to_owned());
+        self
+    }
+
+    /// Picks up an iteration where `state` was taken, with the same sort order and page size.
+    /// The URL of `state` is ignored, so a checkpoint can't send requests, and the credentials
+    /// of the client, anywhere else.
+    pub fn resume_from(mut self, state: PageIteratorState) -> Self {
+        self.sort_order = state.sort_order;
+        self.page_size = state.page_size;
+        self.iteration_started = state.is_finished || state.cursor.is_some();
+        self.previous_cursor = state.previous_cursor;
+        self.next_cursor = state.cursor;
+        self
+    }
+
+    /// The cursor of the next page, `None` before the first page and after the last one.
+    pub fn cursor(&self) -> Option<&str> {
+        self.next_cursor.as_deref()
+    }
+
+    pub fn state(&self) -> PageIteratorState {
+        PageIteratorState {
+            url: self.url.clone(),
+            sort_order: self.sort_order,
+            page_size: self.page_size,
+            cursor: self.next_cursor.clone(),
+            previous_cursor: self.previous_cursor.clone(),
+            is_finished: self.iteration_started && self.next_cursor.is_none(),
+        }
+    }
+
+    /// Fetches the next page, or returns `None` once the last one was fetched.
+    pub async fn next_page(&mut self) -> RobloxResult<Option<Page<U>>> {
         // Just checking for self.next_cursor.is_none() would make single-page sized results return no data. This logic allows for fetching atleast one page
         if self.iteration_started && self.next_cursor.is_none() {
             return Ok(None);
         }
         self.iteration_started = true;
 
+        let page = self.fetch_page(self.next_cursor.clone().as_deref()).await?;
+        self.move_to(&page);
+        Ok(Some(page))
+    }
+
+    /// Fetches the page before the one last fetched, or returns `None` if that was the first
+    /// page. [PageIterator::next_page] then fetches the page after it again.
+    pub async fn previous_page(&mut self) -> RobloxResult<Option<Page<U>>> {
+        let Some(previous_cursor) = self.previous_cursor.clone() else {
+            return Ok(None);
+        };
+
+        let page = self.fetch_page(Some(&previous_cursor)).await?;
+        self.move_to(&page);
+        Ok(Some(page))
+    }
+
+    fn move_to(&mut self, page: &Page<U>) {
+        self.previous_cursor = page.previous_cursor.clone();
+        self.next_cursor = page.next_cursor.clone();
+    }
+
+    /// Fetches the page of `cursor`, or the first page for `None`, without moving the
+    /// iteration.
+    pub async fn fetch_page(&mut self, cursor: Option<&str>) -> RobloxResult<Page<U>> {
+        // The URL of some endpoints already has a query, e.g. the keyword of a search
+        let separator = if self.url.contains('?') { '&' } else { '?' };
+        let query = api_helper::urlencode(&[
+            ("sortOrder", self.sort_order.serialize()),
+            ("limit", self.page_size.serialize()),
+            ("cursor", cursor.unwrap_or_default().to_owned()),
+        ]);
+        let url = format!("{}{}{}", self.url, separator, query);
+        let started_at = Instant::now();
         let page = api_helper::deserialize_body::<PageResponse<T>>(
-            api_helper::get(format!(
-                "{}?sortOrder={}&limit={}&cursor={}",
-                self.url,
-                self.sort_order.serialize(),
-                self.page_size.serialize(),
-                self.next_cursor.clone().unwrap_or(String::new())
-            ))
+            api_helper::execute(
+                &self.client,
+                Method::GET,
+                url.clone(),
+                None,
+                self.auth,
+                ErrorFormat::Api,
+            )
             .await?,
-        )
-        .await;
+        )?;
+
+        telemetry::page_fetched(
+            &self.client,
+            &url,
+            page.data.len(),
+            page.next_page_cursor.is_some(),
+        );
+
+        // An empty page is the last one, whatever its cursor says
+        let next_cursor = page.next_page_cursor.filter(|_| !page.data.is_empty());
+        Ok(Page {
+            items: page.data.iter().filter_map(&mut self.mapper).collect(),
+            cursor: cursor.map(str::to_owned),
+            previous_cursor: page.previous_page_cursor,
+            next_cursor,
+            latency: started_at.elapsed(),
+        })
+    }
+
+    /// The stream is `Send + 'static`, so it can be moved into a spawned task.
+    pub fn into_stream(self) -> impl Stream<Item = RobloxResult<U>> + Send + 'static {
+        PagesIterator::new(PageFetcher::new(self)).into_stream()
+    }
+
+    /// Like [PageIterator::into_stream], but yields whole pages, e.g. to write them to a
+    /// database in batches. Pages whose items were all filtered out are skipped.
+    pub fn into_pages_stream(self) -> impl Stream<Item = RobloxResult<Page<U>>> + Send + 'static {
+        let mut fetcher = PageFetcher::new(self);
+        stream! {
+            loop {
+                match fetcher.next_page().await {
+                    Ok(Some(page)) => {
+                        if !page.items.is_empty() {
+                            yield Ok(page);
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(error) => {
+                        yield Err(error);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fetches every remaining item.
+    pub async fn collect_all(self) -> RobloxResult<Vec<U>> {
+        self.take_n(usize::MAX).await
+    }
+
+    /// Fetches the next `n` items, or every remaining item if there are fewer. No page is
+    /// fetched once `n` items were received.
+    pub async fn take_n(mut self, n: usize) -> RobloxResult<Vec<U>> {
+        let mut items = Vec::new();
+        while items.len() < n {
+            match self.next_page().await? {
+                Some(page) => items.extend(page.items),
+                None => break,
+            }
+        }
+
+        items.truncate(n);
+        Ok(items)
+    }
+}
+
+// Fetches the pages of a PageIterator one after the other or, with a prefetch depth, from a
+// task spawned on the first page
+struct PageFetcher<T, U>
+where
+    T: serde::de::DeserializeOwned,
+    U: Clone,
+{
+    iterator: Option<PageIterator<T, U>>,
+    prefetched: Option<mpsc::Receiver<RobloxResult<Page<U>>>>,
+}
+
+impl<T, U> PageFetcher<T, U>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+    U: Clone + Send + 'static,
+{
+    fn new(iterator: PageIterator<T, U>) -> Self {
+        Self {
+            iterator: Some(iterator),
+            prefetched: None,
+        }
+    }
+
+    async fn next_page(&mut self) -> RobloxResult<Option<Page<U>>> {
+        if let Some(iterator) = self.iterator.take_if(|iterator| iterator.prefetch > 0) {
+            self.prefetched = Some(spawn_prefetch(iterator));
+        }
+
+        match (&mut self.iterator, &mut self.prefetched) {
+            (Some(iterator), _) => iterator.next_page().await,
+            (None, Some(prefetched)) => prefetched.recv().await.transpose(),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+// A slot of the channel is reserved before each page is fetched, so at most `prefetch` pages
+// are waiting to be consumed. The task is spawned on the runtime of the client, and stops once
+// the receiver is dropped
+fn spawn_prefetch<T, U>(mut iterator: PageIterator<T, U>) -> mpsc::Receiver<RobloxResult<Page<U>>>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+    U: Clone + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel(iterator.prefetch);
+    let client = iterator.client.clone();
+    client.runtime().spawn(Box::pin(async move {
+        while let Ok(permit) = sender.reserve().await {
+            match iterator.next_page().await {
+                Ok(Some(page)) => {
+                    let is_last = !page.has_next();
+                    permit.send(Ok(page));
+                    if is_last {
+                        break;
+                    }
+                }
+                Ok(None) => break,
+                Err(error) => {
+                    permit.send(Err(error));
+                    break;
+                }
+            }
+        }
+    }));
+    receiver
+}
+
+impl<T, U> BasePageIterator<U> for PageFetcher<T, U>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+    U: Clone + Send + 'static,
+{
+    async fn next_page(&mut self) -> RobloxResult<Option<Vec<U>>> {
+        PageFetcher::next_page(self)
+            .await
+            .map(|page| page.map(|page| page.items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use futures_util::StreamExt;
+    use reqwest::{header::HeaderMap, StatusCode};
+    use serde_json::json;
+
+    use super::{
+        super::transport::{Request, Response, Transport, TransportError},
+        *,
+    };
+
+    const MEMBERS_URL: &str = "https://groups.roblox.com/v1/groups/1/users";
+
+    // Serves `pages` of ids, whose cursors are their indices, and keeps the URLs it was sent
+    struct PagesTransport {
+        pages: Vec<Vec<i64>>,
+        urls: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Transport for PagesTransport {
+        async fn send(&self, request: Request) -> Result<Response, TransportError> {
+            self.urls.lock().unwrap().push(request.url.clone());
+            let index = match request.url.split_once("cursor=") {
+                Some((_, cursor)) if !cursor.is_empty() => cursor.parse::<usize>()?,
+                _ => 0,
+            };
+            let body = json!({
+                "previousPageCursor": index.checked_sub(1).map(|index| index.to_string()),
+                "nextPageCursor": (index + 1 < self.pages.len()).then(|| (index + 1).to_string()),
+                "data": self.pages[index],
+            });
+            Ok(Response {
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+                body: body.to_string().into_bytes(),
+            })
+        }
+    }
+
+    fn members(pages: Vec<Vec<i64>>) -> (PageIterator<i64, i64>, Arc<PagesTransport>) {
+        let transport = Arc::new(PagesTransport {
+            pages,
+            urls: Mutex::new(Vec::new()),
+        });
+        let client = RobloxClient::builder()
+            .transport(transport.clone())
+            .roblosecurity("cookie")
+            .build();
+        let iterator = PageIterator::new(client, MEMBERS_URL.to_owned(), identity_mapper);
+        (iterator, transport)
+    }
+
+    #[tokio::test]
+    async fn resuming_keeps_the_url_of_the_iterator() {
+        let (mut iterator, transport) = members(vec![vec![1], vec![2], vec![3]]);
+        iterator = iterator.resume_from(PageIteratorState {
+            url: "https://evil.example/steal".to_owned(),
+            sort_order: SortOrder::Descending,
+            page_size: PageSize::OneHundred,
+            cursor: Some("2".to_owned()),
+            previous_cursor: Some("1".to_owned()),
+            is_finished: false,
+        });
+
+        assert_eq!(iterator.next_page().await.unwrap().unwrap().items, [3]);
+        let urls = transport.urls.lock().unwrap();
+        assert_eq!(
+            *urls,
+            [format!("{}?sortOrder=Desc&limit=100&cursor=2", MEMBERS_URL)]
+        );
+    }
+
+    #[tokio::test]
+    async fn prefetches_at_most_prefetch_pages_ahead() {
+        let (iterator, transport) = members((0..6).map(|index| vec![index]).collect());
+        let mut pages = Box::pin(iterator.prefetch(2).into_pages_stream());
+
+        for consumed in 1..=3 {
+            pages.next().await.unwrap().unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert_eq!(transport.urls.lock().unwrap().len(), consumed + 2);
+        }
+        assert_eq!(pages.collect::<Vec<_>>().await.len(), 3);
+        assert_eq!(transport.urls.lock().unwrap().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn resumes_from_a_saved_state() {
+        let pages = (0..4).map(|index| vec![index]).collect::<Vec<_>>();
+        let (mut iterator, _) = members(pages.clone());
+        iterator = iterator.sort_order(SortOrder::Descending);
+        iterator.next_page().await.unwrap();
+        iterator.next_page().await.unwrap();
+        let state = serde_json::to_string(&iterator.state()).unwrap();
+
+        let (resumed, transport) = members(pages);
+        let resumed =
+            resumed.resume_from(serde_json::from_str::<PageIteratorState>(&state).unwrap());
+        assert_eq!(resumed.state(), iterator.state());
+        assert_eq!(resumed.collect_all().await.unwrap(), [2, 3]);
+        assert!(transport.urls.lock().unwrap()[0].contains("sortOrder=Desc"));
+        assert_eq!(iterator.collect_all().await.unwrap(), [2, 3]);
+    }
+
+    #[tokio::test]
+    async fn goes_back_a_page_and_forward_again() {
+        let (mut iterator, _) = members((0..4).map(|index| vec![index]).collect());
+        for _ in 0..3 {
+            iterator.next_page().await.unwrap();
+        }
+
+        let previous = iterator.previous_page().await.unwrap().unwrap();
+        assert_eq!(previous.items, [1]);
+        assert_eq!(previous.cursor.as_deref(), Some("1"));
+        assert_eq!(iterator.next_page().await.unwrap().unwrap().items, [2]);
+        assert_eq!(iterator.next_page().await.unwrap().unwrap().items, [3]);
+        assert!(iterator.next_page().await.unwrap().is_none());
+
+        let (mut iterator, _) = members(vec![vec![0], vec![1]]);
+        iterator.next_page().await.unwrap();
+        assert!(iterator.previous_page().await.unwrap().is_none());
+    }
 
-        self.next_cursor = page.next_page_cursor;
+    #[tokio::test]
+    async fn skips_pages_whose_items_were_all_filtered_out() {
+        let pages = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+        let (iterator, _) = members(pages.clone());
+        let items = iterator
+            .filter_map(|id| (id != 3 && id != 4).then_some(id))
+            .into_stream()
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            items.into_iter().collect::<RobloxResult<Vec<_>>>().unwrap(),
+            [1, 2, 5, 6]
+        );
 
-        Ok(Some(page.data.iter().map(self.mapper).collect()))
+        let (iterator, _) = members(pages);
+        let pages = iterator
+            .filter_map(|id| (id != 3 && id != 4).then_some(id))
+            .into_pages_stream()
+            .map(|page| page.unwrap().items)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(pages, [vec![1, 2], vec![5, 6]]);
     }
 }
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
-use crate::models::{GroupRole, Presence};
+use crate::models::{GroupRole, Presence, SkinnyUser};
 
 use super::{parsers::parse_iso8601_date, ApiError};
 
@@ -10,11 +10,26 @@ pub struct ErrorResponse {
     pub errors: Vec<ApiError>,
 }
 
+// The error body of an OAuth 2.0 endpoint (RFC 6749, section 5.2)
+#[derive(Deserialize)]
+pub struct OAuthErrorResponse {
+    pub error: String,
+    pub error_description: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct ApiArrayResponse<T> {
     pub data: Vec<T>,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsernameUserResponse {
+    pub requested_username: String,
+    #[serde(flatten)]
+    pub user: SkinnyUser,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserPresencesResponse {
@@ -50,6 +65,7 @@ pub struct UniverseFavoriteCountResponse {
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct AssetCreatorResponse {
+    #[allow(dead_code)]
     pub id: i64,
     pub name: String,
     pub creator_type: String,
@@ -70,6 +86,7 @@ pub struct UniverseCreatorResponse {
 #[derive(Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PresenceLastOnline {
+    #[allow(dead_code)]
     pub user_id: i64,
     #[serde(deserialize_with = "parse_iso8601_date")]
     pub last_online: DateTime<Utc>,
@@ -0,0 +1,216 @@
+use std::time::Duration;
+
+use reqwest::Method;
+
+use super::RobloxError;
+
+/// Decides which failed requests are sent again, and how long to wait before
+/// each new attempt.
+///
+/// A request is retried when Roblox rate limits it, answers with a 5xx status,
+/// or when it could not be sent at all. The delay between attempts grows
+/// exponentially, and a `Retry-After` header sent by Roblox takes precedence
+/// over it. By default, only idempotent verbs (GET, HEAD, OPTIONS, PUT, DELETE)
+/// are retried, since sending a POST or PATCH again could apply it twice.
+///
+/// ```
+/// use std::time::Duration;
+/// use oxid_roblox::{util::RetryPolicy, RobloxClient};
+///
+/// let client = RobloxClient::builder()
+///     .retry_policy(
+///         RetryPolicy::default()
+///             .max_attempts(5)
+///             .initial_backoff(Duration::from_secs(1)),
+///     )
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request exactly once.
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// The maximum amount of times a request is sent, including the first attempt.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// The delay before the first retry. It doubles with every following retry.
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// The upper bound of the delay between two attempts, unless Roblox asks for a longer
+    /// one through `Retry-After`.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Whether delays are randomised (between half and all of the computed delay) so that
+    /// concurrent requests don't retry in lockstep.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Whether POST and PATCH requests are retried too.
+    pub fn retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    // `attempt` is the number of the attempt that just failed, starting at 1
+    pub(crate) fn should_retry(&self, verb: &Method, error: &RobloxError, attempt: u32) -> bool {
+        let is_idempotent = matches!(
+            *verb,
+            Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+        );
+        let is_transient = match error {
+            RobloxError::Transport(_) | RobloxError::RateLimited { .. } => true,
+            RobloxError::Api { status, .. } => status.is_server_error(),
+            _ => false,
+        };
+
+        attempt < self.max_attempts && is_transient && (is_idempotent || self.retry_non_idempotent)
+    }
+
+    pub(crate) fn delay(&self, error: &RobloxError, attempt: u32) -> Duration {
+        if let RobloxError::RateLimited {
+            retry_after: Some(retry_after),
+        } = error
+        {
+            return *retry_after;
+        }
+
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_backoff);
+
+        if self.jitter {
+            backoff / 2 + backoff.mul_f64(fastrand::f64() / 2.0)
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+
+    use super::{
+        super::{transport::TransportError, Credential},
+        *,
+    };
+
+    fn server_error() -> RobloxError {
+        RobloxError::Api {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            errors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn retries_transient_errors_of_idempotent_verbs() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.should_retry(&Method::GET, &server_error(), 1));
+        assert!(policy.should_retry(
+            &Method::DELETE,
+            &RobloxError::from(TransportError::from("connection reset")),
+            1
+        ));
+        assert!(policy.should_retry(
+            &Method::GET,
+            &RobloxError::RateLimited { retry_after: None },
+            2
+        ));
+        assert!(!policy.should_retry(&Method::GET, &server_error(), 3));
+        assert!(!policy.should_retry(&Method::POST, &server_error(), 1));
+        assert!(!policy.should_retry(
+            &Method::GET,
+            &RobloxError::Api {
+                status: StatusCode::BAD_REQUEST,
+                errors: Vec::new(),
+            },
+            1
+        ));
+        assert!(!policy.should_retry(
+            &Method::GET,
+            &RobloxError::Unauthorized(Some(Credential::Roblosecurity)),
+            1
+        ));
+    }
+
+    #[test]
+    fn retries_non_idempotent_verbs_when_allowed() {
+        let policy = RetryPolicy::default().retry_non_idempotent(true);
+        assert!(policy.should_retry(&Method::POST, &server_error(), 1));
+        assert!(policy.should_retry(&Method::PATCH, &server_error(), 1));
+    }
+
+    #[test]
+    fn none_never_retries() {
+        assert!(!RetryPolicy::none().should_retry(&Method::GET, &server_error(), 1));
+    }
+
+    #[test]
+    fn backs_off_exponentially_up_to_the_maximum() {
+        let policy = RetryPolicy::default()
+            .jitter(false)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(350));
+
+        assert_eq!(policy.delay(&server_error(), 1), Duration::from_millis(100));
+        assert_eq!(policy.delay(&server_error(), 2), Duration::from_millis(200));
+        assert_eq!(policy.delay(&server_error(), 3), Duration::from_millis(350));
+        assert_eq!(
+            policy.delay(&server_error(), 40),
+            Duration::from_millis(350)
+        );
+    }
+
+    #[test]
+    fn jitter_keeps_at_least_half_of_the_backoff() {
+        let policy = RetryPolicy::default().initial_backoff(Duration::from_millis(100));
+        for _ in 0..100 {
+            let delay = policy.delay(&server_error(), 1);
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn retry_after_takes_precedence() {
+        let policy = RetryPolicy::default().max_backoff(Duration::from_secs(1));
+        let error = RobloxError::RateLimited {
+            retry_after: Some(Duration::from_secs(60)),
+        };
+        assert_eq!(policy.delay(&error, 1), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,200 @@
+//! The async runtime a client waits and runs background tasks on
+//!
+//! Besides sending requests through its [Transport](super::transport::Transport),
+//! a client sleeps between the attempts of a retried request and before
+//! dispatching the batches of its loaders, and spawns tasks for those batches
+//! and for [prefetching](super::paging::PageIterator::prefetch) pages. By
+//! default it uses Tokio through [TokioRuntime], which must then be running.
+//! Any other executor can be used by implementing [Runtime] and passing it to
+//! [RobloxClientBuilder::runtime](crate::RobloxClientBuilder::runtime), along
+//! with a transport for its HTTP stack:
+//!
+//! ```
+//! # mod smol {
+//! #     pub struct Task;
+//! #     impl Task { pub fn detach(self) {} }
+//! #     pub fn spawn<F: std::future::Future + Send + 'static>(_: F) -> Task { Task }
+//! #     pub struct Timer;
+//! #     impl Timer { pub async fn after(_: std::time::Duration) {} }
+//! # }
+//! use std::time::Duration;
+//!
+//! use async_trait::async_trait;
+//! use futures_util::future::BoxFuture;
+//! use oxid_roblox::{util::runtime::Runtime, RobloxClient};
+//!
+//! struct SmolRuntime;
+//!
+//! #[async_trait]
+//! impl Runtime for SmolRuntime {
+//!     async fn sleep(&self, duration: Duration) {
+//!         smol::Timer::after(duration).await;
+//!     }
+//!
+//!     fn spawn(&self, task: BoxFuture<'static, ()>) {
+//!         smol::spawn(task).detach();
+//!     }
+//! }
+//!
+//! let client = RobloxClient::builder().runtime(SmolRuntime).build();
+//! ```
+//!
+//! The blocking client always runs on a Tokio runtime of its own.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+
+/// Timers and task spawning for a client. See the [module](self) documentation.
+#[async_trait]
+pub trait Runtime: Send + Sync {
+    /// Completes once `duration` has elapsed.
+    async fn sleep(&self, duration: Duration);
+
+    /// Runs `task` in the background. The client doesn't wait for it, and drops nothing it
+    /// needs if the task never completes.
+    fn spawn(&self, task: BoxFuture<'static, ()>);
+}
+
+/// The default [Runtime], which uses the Tokio runtime the client is called from. Sleeping or
+/// spawning outside of one panics.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioRuntime;
+
+#[async_trait]
+impl Runtime for TokioRuntime {
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+
+    fn spawn(&self, task: BoxFuture<'static, ()>) {
+        tokio::spawn(task);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        future::Future,
+        pin::pin,
+        sync::{Arc, Mutex},
+        task::{Context, Poll, Wake, Waker},
+        thread::{self, Thread},
+    };
+
+    use futures_util::StreamExt;
+    use reqwest::{header::HeaderMap, StatusCode};
+    use tokio::sync::oneshot;
+
+    use super::{
+        super::{
+            transport::{Request, Response, Transport, TransportError},
+            RetryPolicy,
+        },
+        *,
+    };
+    use crate::{derives::UserDerive, RobloxClient};
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    // Polls `future` on the current thread, without any Tokio runtime
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut context = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    // Runs every task and timer on a thread of its own
+    struct ThreadRuntime;
+
+    #[async_trait]
+    impl Runtime for ThreadRuntime {
+        async fn sleep(&self, duration: Duration) {
+            let (sender, receiver) = oneshot::channel();
+            thread::spawn(move || {
+                thread::sleep(duration);
+                let _ = sender.send(());
+            });
+            let _ = receiver.await;
+        }
+
+        fn spawn(&self, task: BoxFuture<'static, ()>) {
+            thread::spawn(move || block_on(task));
+        }
+    }
+
+    // Fails the first request to each URL with a 503, and answers the others with a user or a
+    // page of usernames
+    #[derive(Default)]
+    struct FlakyTransport {
+        urls: Mutex<HashSet<String>>,
+    }
+
+    #[async_trait]
+    impl Transport for FlakyTransport {
+        async fn send(&self, request: Request) -> Result<Response, TransportError> {
+            let is_first = self.urls.lock().unwrap().insert(request.url.clone());
+            let (status, body) = match is_first {
+                true => (StatusCode::SERVICE_UNAVAILABLE, String::new()),
+                false if request.url.contains("/username-history") => (
+                    StatusCode::OK,
+                    r#"{"nextPageCursor":null,"data":[{"name":"old"}]}"#.to_owned(),
+                ),
+                false => (
+                    StatusCode::OK,
+                    r#"{"data":[{"id":1,"name":"user","displayName":"user"}]}"#.to_owned(),
+                ),
+            };
+            Ok(Response {
+                status,
+                headers: HeaderMap::new(),
+                body: body.into_bytes(),
+            })
+        }
+    }
+
+    #[test]
+    fn runs_without_tokio() {
+        let client = RobloxClient::builder()
+            .transport(FlakyTransport::default())
+            .runtime(ThreadRuntime)
+            .retry_policy(
+                RetryPolicy::default()
+                    .jitter(false)
+                    .initial_backoff(Duration::from_millis(1))
+                    .retry_non_idempotent(true),
+            )
+            .build();
+
+        // Retried once, through a loader batch
+        let user = block_on(client.load_user(1)).unwrap().unwrap();
+        assert_eq!(user.id, 1);
+
+        // Retried once, from a prefetching task
+        let names = block_on(
+            crate::base_user(1)
+                .username_history(&client)
+                .prefetch(1)
+                .into_stream()
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            names.into_iter().collect::<Result<Vec<_>, _>>().unwrap(),
+            ["old"]
+        );
+    }
+}
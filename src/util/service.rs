@@ -0,0 +1,57 @@
+/// A family of Roblox web API endpoints, each hosted on its own subdomain of
+/// roblox.com (`https://users.roblox.com`, `https://groups.roblox.com`, ...).
+///
+/// The base URL each service is reached through can be changed per client with
+/// [RobloxClientBuilder::base_domain](crate::RobloxClientBuilder::base_domain) and
+/// [RobloxClientBuilder::service_base_url](crate::RobloxClientBuilder::service_base_url).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RobloxService {
+    Apis,
+    Auth,
+    Badges,
+    Develop,
+    Economy,
+    Friends,
+    Games,
+    Groups,
+    PremiumFeatures,
+    Presence,
+    TwoStepVerification,
+    Users,
+}
+
+impl RobloxService {
+    pub const ALL: [RobloxService; 12] = [
+        RobloxService::Apis,
+        RobloxService::Auth,
+        RobloxService::Badges,
+        RobloxService::Develop,
+        RobloxService::Economy,
+        RobloxService::Friends,
+        RobloxService::Games,
+        RobloxService::Groups,
+        RobloxService::PremiumFeatures,
+        RobloxService::Presence,
+        RobloxService::TwoStepVerification,
+        RobloxService::Users,
+    ];
+
+    /// The subdomain the service is hosted on, e.g. `users` for `https://users.roblox.com`.
+    pub fn subdomain(self) -> &'static str {
+        match self {
+            RobloxService::Apis => "apis",
+            RobloxService::Auth => "auth",
+            RobloxService::Badges => "badges",
+            RobloxService::Develop => "develop",
+            RobloxService::Economy => "economy",
+            RobloxService::Friends => "friends",
+            RobloxService::Games => "games",
+            RobloxService::Groups => "groups",
+            RobloxService::PremiumFeatures => "premiumfeatures",
+            RobloxService::Presence => "presence",
+            RobloxService::TwoStepVerification => "twostepverification",
+            RobloxService::Users => "users",
+        }
+    }
+}
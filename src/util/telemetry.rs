@@ -0,0 +1,134 @@
+// Instrumentation of requests, compiled to no-ops unless the `tracing` feature is enabled
+
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+use reqwest::Method;
+
+use crate::RobloxClient;
+
+#[cfg(feature = "tracing")]
+use super::RobloxError;
+use super::{challenge::Challenge, transport::Response, RobloxResult};
+
+// The endpoint of a URL with its ids replaced, e.g. "/v1/groups/{id}/users" for
+// "https://groups.roblox.com/v1/groups/1/users?limit=10", so that requests to the same
+// endpoint can be grouped together
+#[cfg(feature = "tracing")]
+pub(crate) fn endpoint_template(client: &RobloxClient, url: &str) -> String {
+    let path = client.service_of(url).map_or(url, |(_, path)| path);
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    path.split('/')
+        .map(|segment| {
+            if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// The span of a request, covering every attempt of it
+pub(crate) struct RequestSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    started_at: Instant,
+}
+
+impl RequestSpan {
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn new(client: &RobloxClient, verb: &Method, url: &str) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "roblox_request",
+                method = %verb,
+                service = client
+                    .service_of(url)
+                    .map(|(service, _)| tracing::field::debug(service)),
+                endpoint = %endpoint_template(client, url),
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                retries = tracing::field::Empty,
+            ),
+            #[cfg(feature = "tracing")]
+            started_at: Instant::now(),
+        }
+    }
+
+    pub(crate) async fn instrument<F: std::future::Future>(&self, future: F) -> F::Output {
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(future, self.span.clone());
+        future.await
+    }
+
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn finish(&self, result: &RobloxResult<Response>, retries: u32) {
+        #[cfg(feature = "tracing")]
+        {
+            let status = match result {
+                Ok(response) => Some(response.status),
+                Err(RobloxError::Api { status, .. } | RobloxError::OAuth { status, .. }) => {
+                    Some(*status)
+                }
+                Err(RobloxError::Unauthorized(_)) => Some(reqwest::StatusCode::UNAUTHORIZED),
+                Err(RobloxError::RateLimited { .. }) => {
+                    Some(reqwest::StatusCode::TOO_MANY_REQUESTS)
+                }
+                Err(_) => None,
+            };
+            if let Some(status) = status {
+                self.span.record("status", status.as_u16());
+            }
+            self.span
+                .record("latency_ms", self.started_at.elapsed().as_millis() as u64);
+            self.span.record("retries", retries);
+
+            let _entered = self.span.enter();
+            match result {
+                Ok(_) => tracing::debug!("request succeeded"),
+                Err(error) => tracing::warn!(%error, "request failed"),
+            }
+        }
+    }
+}
+
+// The token itself is never logged
+pub(crate) fn csrf_token_refreshed() {
+    #[cfg(feature = "tracing")]
+    tracing::debug!("refreshed the x-csrf-token");
+}
+
+// The new cookie itself is never logged
+pub(crate) fn roblosecurity_rotated() {
+    #[cfg(feature = "tracing")]
+    tracing::info!("roblox rotated the .ROBLOSECURITY cookie");
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn challenge_received(challenge: &Challenge) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        challenge_type = challenge.challenge_type.as_str(),
+        "roblox asked for a challenge to be solved"
+    );
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn page_fetched(
+    client: &RobloxClient,
+    url: &str,
+    item_count: usize,
+    has_next_page: bool,
+) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        endpoint = %endpoint_template(client, url),
+        item_count,
+        has_next_page,
+        "fetched a page"
+    );
+}
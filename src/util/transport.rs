@@ -0,0 +1,261 @@
+//! The HTTP layer every request of a [RobloxClient](crate::RobloxClient) goes through
+//!
+//! By default, requests are sent with [reqwest] through a [ReqwestTransport].
+//! Any other HTTP stack can be used by implementing [Transport] and passing it
+//! to [RobloxClientBuilder::transport](crate::RobloxClientBuilder::transport).
+//! For example, an in-memory fake for tests:
+//!
+//! ```
+//! use async_trait::async_trait;
+//! use oxid_roblox::{
+//!     util::transport::{Request, Response, Transport, TransportError},
+//!     RobloxClient,
+//! };
+//! use reqwest::{header::HeaderMap, StatusCode};
+//!
+//! struct FakeTransport;
+//!
+//! #[async_trait]
+//! impl Transport for FakeTransport {
+//!     async fn send(&self, request: Request) -> Result<Response, TransportError> {
+//!         assert_eq!(request.url, "https://badges.roblox.com/v1/badges/1");
+//!
+//!         Ok(Response {
+//!             status: StatusCode::NOT_FOUND,
+//!             headers: HeaderMap::new(),
+//!             body: br#"{"errors":[{"code":1,"message":"Badge is invalid or does not exist."}]}"#.to_vec(),
+//!         })
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = RobloxClient::builder().transport(FakeTransport).build();
+//!     assert!(client.badge_from_id(1).await.is_err());
+//! }
+//! ```
+
+use std::{fmt, sync::Arc};
+
+use async_trait::async_trait;
+use reqwest::{header::HeaderMap, Client, Method, StatusCode};
+use serde_json::Value;
+
+/// The error a [Transport] returns when a request could not be sent or its
+/// response could not be read.
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+// Headers whose values are secrets, and are left out of the Debug output of requests and responses
+pub(crate) const SENSITIVE_HEADERS: [&str; 6] = [
+    "authorization",
+    "cookie",
+    "rblx-challenge-metadata",
+    "set-cookie",
+    "x-api-key",
+    "x-csrf-token",
+];
+
+// Fields whose values are secrets, and are left out of JSON and form-encoded bodies
+const SENSITIVE_FIELDS: [&str; 6] = [
+    "access_token",
+    "client_secret",
+    "code_verifier",
+    "id_token",
+    "refresh_token",
+    "verificationToken",
+];
+
+// Fields of request bodies whose values are secrets, such as the authorization code of an
+// OAuth request or the code of a 2-step verification. Their names are too generic to be
+// redacted from responses, e.g. the code of an Open Cloud error
+const SENSITIVE_REQUEST_FIELDS: [&str; 2] = ["code", "token"];
+
+pub(crate) const REDACTED: &str = "[REDACTED]";
+
+// The body of a response as a string, without the values of its sensitive fields. Bodies
+// without any are left untouched
+pub(crate) fn redact_body(body: &[u8]) -> String {
+    redact_fields(body, |name| SENSITIVE_FIELDS.contains(&name))
+}
+
+// Like redact_body, for the body of a request
+pub(crate) fn redact_request_body(body: &[u8]) -> String {
+    redact_fields(body, |name| {
+        SENSITIVE_FIELDS.contains(&name) || SENSITIVE_REQUEST_FIELDS.contains(&name)
+    })
+}
+
+fn redact_fields(body: &[u8], is_sensitive: impl Fn(&str) -> bool) -> String {
+    let body = String::from_utf8_lossy(body);
+
+    if let Ok(Value::Object(mut object)) = serde_json::from_str::<Value>(&body) {
+        let mut is_redacted = false;
+        for (name, value) in object.iter_mut() {
+            if is_sensitive(name) {
+                *value = Value::from(REDACTED);
+                is_redacted = true;
+            }
+        }
+        return match is_redacted {
+            true => Value::Object(object).to_string(),
+            false => body.into_owned(),
+        };
+    }
+
+    body.split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) if is_sensitive(name) => format!("{}={}", name, REDACTED),
+            _ => pair.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+struct RedactedHeaders<'a>(&'a HeaderMap);
+
+impl fmt::Debug for RedactedHeaders<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(name, value)| {
+                let value: &dyn fmt::Debug =
+                    if value.is_sensitive() || SENSITIVE_HEADERS.contains(&name.as_str()) {
+                        &REDACTED
+                    } else {
+                        value
+                    };
+                (name, value)
+            }))
+            .finish()
+    }
+}
+
+/// An HTTP request, as handed to a [Transport]. Its `Debug` output leaves out the values of
+/// the headers that hold credentials, and of the OAuth secrets in its body.
+#[derive(Clone)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+impl fmt::Debug for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Request")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("headers", &RedactedHeaders(&self.headers))
+            .field("body", &self.body.as_deref().map(redact_body))
+            .finish()
+    }
+}
+
+/// An HTTP response, with its body fully read. Like [Request], its `Debug` output leaves out
+/// the values of the headers and body fields that hold credentials.
+#[derive(Clone)]
+pub struct Response {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl fmt::Debug for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Response")
+            .field("status", &self.status)
+            .field("headers", &RedactedHeaders(&self.headers))
+            .field("body", &redact_body(&self.body))
+            .finish()
+    }
+}
+
+/// Sends HTTP requests on behalf of a [RobloxClient](crate::RobloxClient).
+///
+/// A transport only moves bytes: status codes, x-csrf-tokens, retries and
+/// deserialization are all handled by the client.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    async fn send(&self, request: Request) -> Result<Response, TransportError>;
+}
+
+#[async_trait]
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    async fn send(&self, request: Request) -> Result<Response, TransportError> {
+        self.as_ref().send(request).await
+    }
+}
+
+/// The default [Transport], backed by a [reqwest::Client].
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<Client> for ReqwestTransport {
+    fn from(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: Request) -> Result<Response, TransportError> {
+        let mut builder = self
+            .client
+            .request(request.method, request.url)
+            .headers(request.headers);
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let response = builder.send().await?;
+        Ok(Response {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.bytes().await?.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_secrets_from_json_bodies() {
+        let body = redact_body(br#"{"access_token":"access-value","expires_in":3600}"#);
+        assert!(!body.contains("access-value"), "{}", body);
+        assert!(body.contains("3600"), "{}", body);
+    }
+
+    #[test]
+    fn redacts_codes_and_tokens_from_requests() {
+        let body = redact_request_body(
+            b"grant_type=authorization_code&code=code-value&code_verifier=verifier-value",
+        );
+        assert_eq!(
+            body,
+            "grant_type=authorization_code&code=[REDACTED]&code_verifier=[REDACTED]"
+        );
+        assert_eq!(
+            redact_request_body(b"token=token-value"),
+            "token=[REDACTED]"
+        );
+
+        let body = redact_request_body(br#"{"challengeId":"id-value","code":"123456"}"#);
+        assert!(!body.contains("123456"), "{}", body);
+        assert!(body.contains("id-value"), "{}", body);
+    }
+
+    #[test]
+    fn keeps_the_code_of_json_errors() {
+        let body = br#"{"code":"NOT_FOUND","message":"User not found."}"#;
+        assert_eq!(redact_body(body), String::from_utf8_lossy(body));
+    }
+}